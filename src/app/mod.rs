@@ -1,6 +1,7 @@
 pub use start::start;
 
 pub mod cli;
+mod preview;
 mod server;
 mod start;
 mod tile_coord;
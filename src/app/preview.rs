@@ -0,0 +1,58 @@
+use crate::{
+    app::{server::tile_bounds_to_epsg3857, tile_coord::TileCoord},
+    render::{ImageFormat, RenderLayer, RenderRequest, RenderWorkerPool},
+};
+use std::{collections::HashSet, path::Path};
+
+/// Sample tiles covering diverse terrain: `(name, zoom, x, y)`.
+const SAMPLE_TILES: &[(&str, u8, u32, u32)] = &[
+    ("overview_slovakia", 8, 141, 88),
+    ("water_orava", 12, 2270, 1399),
+    ("ridge_low_tatras", 13, 4541, 2815),
+    ("alpine_high_tatras", 14, 9106, 5614),
+    ("terrain_slovak_paradise", 15, 18239, 11261),
+    ("urban_bratislava", 16, 35882, 22742),
+];
+
+const SAMPLE_SCALES: &[f64] = &[1.0, 2.0];
+
+/// Renders the sample tiles into `out_dir` as `{name}_{zoom}_{x}_{y}@{scale}x.jpeg`.
+pub async fn render_preview(
+    render_worker_pool: &RenderWorkerPool,
+    render: &HashSet<RenderLayer>,
+    out_dir: &Path,
+) -> Result<(), String> {
+    tokio::fs::create_dir_all(out_dir)
+        .await
+        .map_err(|err| format!("create {}: {err}", out_dir.display()))?;
+
+    for &(name, zoom, x, y) in SAMPLE_TILES {
+        let coord = TileCoord { zoom, x, y };
+
+        for &scale in SAMPLE_SCALES {
+            let request = RenderRequest::new(
+                tile_bounds_to_epsg3857(x, y, zoom, 256),
+                zoom,
+                scale,
+                ImageFormat::Jpeg,
+                render.clone(),
+                None,
+            );
+
+            let rendered = render_worker_pool
+                .render(request)
+                .await
+                .map_err(|err| format!("render {name} ({coord}@{scale}): {err}"))?;
+
+            let path = out_dir.join(format!("{name}_{zoom}_{x}_{y}@{scale}x.jpeg"));
+
+            tokio::fs::write(&path, rendered)
+                .await
+                .map_err(|err| format!("write {}: {err}", path.display()))?;
+
+            println!("Rendered {}", path.display());
+        }
+    }
+
+    Ok(())
+}
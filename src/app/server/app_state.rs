@@ -1,27 +1,45 @@
 use crate::{
-    app::{server::export_route::ExportState, tile_processing_worker::TileProcessingWorker},
-    render::{RenderLayer, RenderWorkerPool},
+    app::{
+        server::{
+            export_route::ExportState, health_route::HealthState, tile_route::TileNotFoundBehavior,
+        },
+        tile_processing_worker::TileProcessingWorker,
+    },
+    render::{PoiFilter, RenderLayer, RenderWorkerPool},
 };
+use deadpool_postgres::Pool;
 use geo::Geometry;
 use std::{collections::HashSet, path::PathBuf, sync::Arc};
 
 #[derive(Clone)]
 pub struct TileVariantState {
+    /// Path prefix of the tile URLs, empty for the root.
+    pub(crate) url_path: String,
     pub(crate) tile_cache_base_path: Option<PathBuf>,
     pub(crate) coverage_geometry: Option<Arc<Geometry>>,
     pub(crate) render: HashSet<RenderLayer>,
+    pub(crate) max_zoom: u8,
+    pub(crate) poi_filter: Option<Arc<PoiFilter>>,
+    pub(crate) cache_control: Option<String>,
 }
 
 #[derive(Clone)]
 pub struct AppState {
     pub(crate) render_worker_pool: Arc<RenderWorkerPool>,
     pub(crate) export_state: Arc<ExportState>,
+    pub(crate) health_state: Arc<HealthState>,
     pub(crate) tile_variants: Arc<Vec<TileVariantState>>,
     pub(crate) default_render: HashSet<RenderLayer>,
     pub(crate) tile_worker: Option<TileProcessingWorker>,
     pub(crate) serve_cached: bool,
-    pub(crate) max_zoom: u8,
+    pub(crate) cache_dir_hash_depth: u8,
+    pub(crate) max_highlight_features: usize,
     pub(crate) allowed_scales: Vec<f64>,
+    pub(crate) default_scale: f64,
+    pub(crate) dpr_hint: bool,
+    pub(crate) debug_headers: bool,
+    pub(crate) db_pool: Pool,
+    pub(crate) tile_not_found_behavior: TileNotFoundBehavior,
 }
 
 #[derive(Clone)]
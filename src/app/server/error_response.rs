@@ -0,0 +1,12 @@
+use axum::{
+    body::Body,
+    http::{Response, StatusCode},
+};
+
+/// Plain-text error response of the JSON routes.
+pub fn error_response(status: StatusCode, message: &'static str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::from(message))
+        .expect("body should be built")
+}
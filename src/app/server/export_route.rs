@@ -2,7 +2,7 @@ use crate::{
     app::server::app_state::AppState,
     render::{
         CustomLayer, CustomLayerOrder, Decorations, Glow, ImageFormat, LabelStyle, RenderLayer,
-        RenderRequest, RenderWorkerPool, bbox_size_in_pixels,
+        RenderRequest, RenderWorkerPool, bbox_size_in_pixels, scale_denominator,
     },
 };
 use axum::{
@@ -38,15 +38,22 @@ pub struct ExportState {
     jobs: Mutex<HashMap<String, Arc<ExportJob>>>,
     semaphore: Arc<Semaphore>,
     max_pixels: u64,
+    max_image_pixels: u64,
     abandon_grace: Duration,
 }
 
 impl ExportState {
-    pub(crate) fn new(max_parallel: usize, max_pixels: u64, abandon_grace: Duration) -> Self {
+    pub(crate) fn new(
+        max_parallel: usize,
+        max_pixels: u64,
+        max_image_pixels: u64,
+        abandon_grace: Duration,
+    ) -> Self {
         Self {
             jobs: Mutex::new(HashMap::new()),
             semaphore: Arc::new(Semaphore::new(max_parallel.max(1))),
             max_pixels,
+            max_image_pixels,
             abandon_grace,
         }
     }
@@ -56,6 +63,8 @@ struct ExportJob {
     file_path: PathBuf,
     filename: String,
     content_type: &'static str,
+    /// OGC scale denominator of the export at the equator.
+    scale_denominator: f64,
     status: Arc<Mutex<ExportStatus>>,
     notify: Arc<Notify>,
     poller_count: Arc<AtomicUsize>,
@@ -193,8 +202,16 @@ pub struct TokenQuery {
     token: String,
 }
 
+/// Query parameters of the export request.
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    /// `scalebar=1` is a shorthand for `decorations.scaleBar` in the body.
+    scalebar: Option<u8>,
+}
+
 pub async fn post(
     State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
     Json(request): Json<ExportRequest>,
 ) -> Response<Body> {
     let (format, ext, content_type) = match parse_format(request.format.as_deref()) {
@@ -219,6 +236,24 @@ pub async fn post(
         (size.width as u64) * (size.height as u64)
     };
 
+    // the scaled image is held in memory as a whole
+    let image_pixels = estimated as f64 * scale * scale;
+
+    if image_pixels > state.export_state.max_image_pixels as f64 {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                json!({
+                    "error": "image_too_large",
+                    "imagePixels": image_pixels.ceil() as u64,
+                    "maxImagePixels": state.export_state.max_image_pixels,
+                })
+                .to_string(),
+            ))
+            .expect("too large body");
+    }
+
     if estimated > max_pixels {
         return Response::builder()
             .status(StatusCode::PAYLOAD_TOO_LARGE)
@@ -328,23 +363,22 @@ pub async fn post(
         None
     };
 
-    render_request.decorations = request.decorations.as_ref().and_then(|d| {
-        let trimmed = |s: &Option<String>| {
-            s.as_deref()
+    render_request.decorations = {
+        let d = request.decorations.as_ref();
+
+        let trimmed = |s: Option<&String>| {
+            s.map(String::as_str)
                 .map(str::trim)
                 .filter(|s| !s.is_empty())
                 .map(str::to_string)
         };
 
-        let scale_bar = d.scale_bar.unwrap_or(false);
-        let north_arrow = trimmed(&d.north_arrow);
-        let attribution = trimmed(&d.attribution);
-
-        if !scale_bar && north_arrow.is_none() && attribution.is_none() {
-            return None;
-        }
+        let scale_bar = query.scalebar.is_some_and(|scalebar| scalebar != 0)
+            || d.and_then(|d| d.scale_bar).unwrap_or(false);
+        let north_arrow = trimmed(d.and_then(|d| d.north_arrow.as_ref()));
+        let attribution = trimmed(d.and_then(|d| d.attribution.as_ref()));
 
-        Some(Decorations {
+        (scale_bar || north_arrow.is_some() || attribution.is_some()).then(|| Decorations {
             scale_bar,
             north_arrow,
             attribution,
@@ -352,7 +386,7 @@ pub async fn post(
             // Web-Mercator scale for the scale bar.
             center_lat: f64::midpoint(request.bbox[1], request.bbox[3]),
         })
-    });
+    };
 
     let job = spawn_export_job(
         state.render_worker_pool.clone(),
@@ -431,6 +465,10 @@ pub async fn get(State(state): State<AppState>, Query(query): Query<TokenQuery>)
             "Content-Disposition",
             format!("attachment; filename=\"{}\"", job.filename),
         )
+        .header(
+            "X-Scale-Denominator",
+            format!("{:.0}", job.scale_denominator),
+        )
         .body(body)
         .expect("download body")
 }
@@ -504,7 +542,7 @@ fn geojson_to_features(geojson: GeoJson) -> Result<Vec<Feature>, &'static str> {
     }
 }
 
-fn bbox4326_to_3857(bbox: [f64; 4]) -> [f64; 4] {
+pub(super) fn bbox4326_to_3857(bbox: [f64; 4]) -> [f64; 4] {
     let (min_x, min_y) = lon_lat_to_3857(bbox[0], bbox[1]);
     let (max_x, max_y) = lon_lat_to_3857(bbox[2], bbox[3]);
     [min_x, min_y, max_x, max_y]
@@ -564,6 +602,8 @@ fn spawn_export_job(
     let poller_change_clone = Arc::clone(&poller_change);
     let file_path_clone = file_path.clone();
 
+    let scale_denominator = scale_denominator(request.zoom, request.scale);
+
     let handle = tokio::spawn(async move {
         let Some(permit) = wait_for_permit(
             semaphore,
@@ -599,6 +639,7 @@ fn spawn_export_job(
         file_path,
         filename,
         content_type,
+        scale_denominator,
         status,
         notify,
         poller_count,
@@ -0,0 +1,105 @@
+use crate::{
+    app::server::{app_state::AppState, tile_bounds_to_epsg3857},
+    render::{ImageFormat, RenderRequest},
+};
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Response, StatusCode},
+};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Tile rendered by the deep check, in the High Tatras to exercise most layers and icons:
+/// `(zoom, x, y)`.
+const CHECK_TILE: (u8, u32, u32) = (14, 9106, 5614);
+
+/// How long a render check result is reused by the following probes.
+const RENDER_CHECK_TTL: Duration = Duration::from_secs(30);
+
+pub struct HealthState {
+    include_render: bool,
+    last_render_check: Mutex<Option<(Instant, Result<(), String>)>>,
+}
+
+impl HealthState {
+    pub(crate) fn new(include_render: bool) -> Self {
+        Self {
+            include_render,
+            last_render_check: Mutex::new(None),
+        }
+    }
+}
+
+/// Checks the database is reachable and, with `--health-include-render`, that a tile renders.
+pub async fn get(State(state): State<AppState>) -> Response<Body> {
+    let result = match check_db(&state).await {
+        Ok(()) if state.health_state.include_render => check_render(&state).await,
+        result => result,
+    };
+
+    match result {
+        Ok(()) => Response::builder()
+            .status(StatusCode::OK)
+            .header("Cache-Control", "no-store")
+            .body(Body::from("ok"))
+            .expect("body should be built"),
+        Err(err) => Response::builder()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .header("Cache-Control", "no-store")
+            .body(Body::from(err))
+            .expect("body should be built"),
+    }
+}
+
+async fn check_db(state: &AppState) -> Result<(), String> {
+    let client = state
+        .db_pool
+        .get()
+        .await
+        .map_err(|err| format!("database unavailable: {err}"))?;
+
+    client
+        .simple_query("SELECT 1")
+        .await
+        .map_err(|err| format!("database query failed: {err}"))?;
+
+    Ok(())
+}
+
+async fn check_render(state: &AppState) -> Result<(), String> {
+    // held during the render so concurrent probes wait for its result instead of rendering too
+    let mut last_render_check = state.health_state.last_render_check.lock().await;
+
+    if let Some((checked_at, result)) = last_render_check.as_ref()
+        && checked_at.elapsed() < RENDER_CHECK_TTL
+    {
+        return result.clone();
+    }
+
+    let (zoom, x, y) = CHECK_TILE;
+
+    let request = RenderRequest::new(
+        tile_bounds_to_epsg3857(x, y, zoom, 256),
+        zoom,
+        1.0,
+        ImageFormat::Jpeg,
+        state.default_render.clone(),
+        None,
+    );
+
+    let result = state
+        .render_worker_pool
+        .render(request)
+        .await
+        .map(|_| ())
+        .map_err(|err| format!("render failed: {err}"));
+
+    if let Err(err) = &result {
+        eprintln!("Health check {err}");
+    }
+
+    *last_render_check = Some((Instant::now(), result.clone()));
+
+    result
+}
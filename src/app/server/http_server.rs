@@ -0,0 +1,171 @@
+use axum::Router;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo, TokioTimer},
+    server::{conn::auto::Builder, graceful::GracefulShutdown},
+    service::TowerToHyperService,
+};
+use std::{
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    pin::pin,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::net::TcpListener;
+use tokio_rustls::{
+    TlsAcceptor,
+    rustls::{
+        self, ServerConfig,
+        pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    },
+};
+
+/// HTTP protocol settings of the server connections.
+pub struct HttpOptions {
+    /// Accept HTTP/2 next to HTTP/1.1, negotiated by ALPN over TLS and with prior knowledge
+    /// (h2c) otherwise.
+    pub http2: bool,
+    /// Keep HTTP/1.1 connections open for further requests.
+    pub keep_alive: bool,
+    /// Time to receive the request headers, also bounding how long an idle HTTP/1.1
+    /// keep-alive connection is held.
+    pub header_read_timeout: Duration,
+    /// Interval of HTTP/2 pings; connections not acknowledging a ping within it are closed.
+    pub http2_keep_alive_interval: Option<Duration>,
+    pub http2_max_concurrent_streams: Option<u32>,
+    /// Terminates TLS on the connections, plain HTTP is served if `None`.
+    pub tls: Option<TlsAcceptor>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error("error reading TLS certificate {}: {1}", .0.display())]
+    Cert(PathBuf, #[source] rustls::pki_types::pem::Error),
+    #[error("no certificate found in {}", .0.display())]
+    NoCert(PathBuf),
+    #[error("error reading TLS key {}: {1}", .0.display())]
+    Key(PathBuf, #[source] rustls::pki_types::pem::Error),
+    #[error("invalid TLS certificate or key: {0}")]
+    Config(#[from] rustls::Error),
+}
+
+/// Loads the PEM certificate chain and private key, failing early on unreadable or mismatching
+/// files rather than on the first handshake.
+pub fn load_tls(cert_path: &Path, key_path: &Path, http2: bool) -> Result<TlsAcceptor, TlsError> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|err| TlsError::Cert(cert_path.to_owned(), err))?;
+
+    if certs.is_empty() {
+        return Err(TlsError::NoCert(cert_path.to_owned()));
+    }
+
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|err| TlsError::Key(key_path.to_owned(), err))?;
+
+    let mut config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+
+    config.alpn_protocols = if http2 {
+        vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+    } else {
+        vec![b"http/1.1".to_vec()]
+    };
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Serves the router on the listener until `shutdown` resolves, then waits for the open
+/// connections to finish their requests.
+pub(crate) async fn serve(
+    listener: TcpListener,
+    router: Router,
+    options: &HttpOptions,
+    shutdown: impl Future<Output = ()>,
+) -> io::Result<()> {
+    let mut builder = Builder::new(TokioExecutor::new());
+
+    builder
+        .http1()
+        .timer(TokioTimer::new())
+        .keep_alive(options.keep_alive)
+        .header_read_timeout(options.header_read_timeout);
+
+    builder
+        .http2()
+        .timer(TokioTimer::new())
+        .keep_alive_interval(options.http2_keep_alive_interval)
+        .max_concurrent_streams(options.http2_max_concurrent_streams);
+
+    if let Some(interval) = options.http2_keep_alive_interval {
+        builder.http2().keep_alive_timeout(interval);
+    }
+
+    let builder = if options.http2 {
+        builder
+    } else {
+        builder.http1_only()
+    };
+
+    let graceful = GracefulShutdown::new();
+
+    let mut shutdown = pin!(shutdown);
+
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(err) => {
+                    // e.g. too many open files; retrying immediately would spin
+                    eprintln!("Accepting connection failed: {err}");
+
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+
+                    continue;
+                }
+            },
+            () = &mut shutdown => break,
+        };
+
+        let builder = builder.clone();
+
+        let service = TowerToHyperService::new(router.clone());
+
+        let watcher = graceful.watcher();
+
+        let tls = options.tls.clone();
+
+        let handshake_timeout = options.header_read_timeout;
+
+        tokio::spawn(async move {
+            // errors are mostly clients going away
+            let _ = match tls {
+                None => {
+                    watcher
+                        .watch(builder.serve_connection(TokioIo::new(stream), service))
+                        .await
+                }
+                Some(acceptor) => {
+                    // bounded like the headers so stalled handshakes don't pile up
+                    let Ok(Ok(stream)) =
+                        tokio::time::timeout(handshake_timeout, acceptor.accept(stream)).await
+                    else {
+                        return;
+                    };
+
+                    watcher
+                        .watch(builder.serve_connection(TokioIo::new(stream), service))
+                        .await
+                }
+            };
+        });
+    }
+
+    drop(listener);
+
+    graceful.shutdown().await;
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::{
+    app::server::{app_state::AppState, error_response::error_response, tile_bounds_to_epsg3857},
+    render::{Feature, query_layer},
+};
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{Response, StatusCode},
+};
+use geo::{Coord, Geometry, MapCoordsInPlace};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use tokio_postgres::{Row, types::Type};
+
+/// Serves the features a layer selects for a tile as a GeoJSON feature collection in EPSG:4326,
+/// with the selected columns (e.g. tags) as properties. `osm_id` and `type` are always present,
+/// `null` for the layers not selecting them. POIs also get their `image` and `wikimedia_commons`
+/// tags as properties, e.g. for thumbnails in POI popups.
+pub async fn get(
+    State(state): State<AppState>,
+    Path((zoom, x, y, layer)): Path<(u8, u32, u32, String)>,
+) -> Response<Body> {
+    let Some(layer) = layer.strip_suffix(".geojson") else {
+        return error_response(StatusCode::NOT_FOUND, "expected <layer>.geojson");
+    };
+
+    if zoom > 20 || x >= 1 << zoom || y >= 1 << zoom {
+        return error_response(StatusCode::BAD_REQUEST, "invalid tile");
+    }
+
+    let client = match state.db_pool.get().await {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("Layer {layer} features connection failed: {err}");
+
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "database unavailable");
+        }
+    };
+
+    let bbox = tile_bounds_to_epsg3857(x, y, zoom, 256);
+
+    let rows = match query_layer(layer, bbox, zoom, &client).await {
+        Some(Ok(rows)) => rows,
+        Some(Err(err)) => {
+            eprintln!("Layer {layer} features query failed: {err}");
+
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "query error");
+        }
+        None => return error_response(StatusCode::NOT_FOUND, "unknown or unsupported layer"),
+    };
+
+    let mut features = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let mut properties = row_properties(&row);
+
+        // layers of merged or clipped geometries (e.g. sea, borders) have no `osm_id` or `type`
+        for key in ["osm_id", "type"] {
+            properties.entry(key).or_insert(Value::Null);
+        }
+
+        if layer == "pois" {
+            for key in ["image", "wikimedia_commons"] {
+                let value = properties
+                    .get("extra")
+                    .and_then(|extra| extra.get(key))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+
+                properties.insert(key.to_string(), value);
+            }
+        }
+
+        let geometry = match Feature::Row(row).get_geometry() {
+            Ok(mut geometry) => {
+                to_wgs84(&mut geometry);
+
+                json!(geojson::Geometry::from(&geometry))
+            }
+            // e.g. empty geometries
+            Err(_) => Value::Null,
+        };
+
+        features.push(json!({
+            "type": "Feature",
+            "properties": properties,
+            "geometry": geometry,
+        }));
+    }
+
+    let collection = json!({
+        "type": "FeatureCollection",
+        "features": features,
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "application/geo+json")
+        .body(Body::from(collection.to_string()))
+        .expect("body should be built")
+}
+
+/// Non-geometry columns of the row; values of types without a JSON counterpart are `null`.
+fn row_properties(row: &Row) -> Map<String, Value> {
+    row.columns()
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| column.name() != "geometry")
+        .map(|(idx, column)| {
+            let value = match *column.type_() {
+                Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                    json!(row.try_get::<_, Option<&str>>(idx).ok().flatten())
+                }
+                Type::BOOL => json!(row.try_get::<_, Option<bool>>(idx).ok().flatten()),
+                Type::INT2 => json!(row.try_get::<_, Option<i16>>(idx).ok().flatten()),
+                Type::INT4 => json!(row.try_get::<_, Option<i32>>(idx).ok().flatten()),
+                Type::INT8 => json!(row.try_get::<_, Option<i64>>(idx).ok().flatten()),
+                Type::FLOAT4 => json!(row.try_get::<_, Option<f32>>(idx).ok().flatten()),
+                Type::FLOAT8 => json!(row.try_get::<_, Option<f64>>(idx).ok().flatten()),
+                ref typ if typ.name() == "hstore" => json!(
+                    row.try_get::<_, Option<HashMap<String, Option<String>>>>(idx)
+                        .ok()
+                        .flatten()
+                ),
+                _ => Value::Null,
+            };
+
+            (column.name().to_string(), value)
+        })
+        .collect()
+}
+
+fn to_wgs84(geometry: &mut Geometry) {
+    const EARTH_RADIUS: f64 = 6_378_137.0;
+
+    geometry.map_coords_in_place(|Coord { x, y }| Coord {
+        x: (x / EARTH_RADIUS).to_degrees(),
+        y: (2.0 * (y / EARTH_RADIUS).exp().atan() - std::f64::consts::FRAC_PI_2).to_degrees(),
+    });
+}
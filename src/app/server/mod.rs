@@ -1,8 +1,19 @@
+pub use http_server::{HttpOptions, load_tls};
+pub use prerender::PrerenderOptions;
 pub use routes::{ServerOptions, TileVariantOptions, start_server};
+pub use tile_route::{TileNotFoundBehavior, tile_bounds_to_epsg3857};
 
 mod app_state;
+mod error_response;
 mod export_route;
+mod health_route;
+mod http_server;
+mod layer_features_route;
 mod legend_route;
+mod peak_info_route;
+mod prerender;
+mod route_geometry_route;
 mod routes;
 mod tile_route;
+mod tilejson_route;
 mod wmts_route;
@@ -0,0 +1,87 @@
+use crate::{
+    app::server::{app_state::AppState, error_response::error_response},
+    render::PEAK_TIER_SQL,
+};
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{Response, StatusCode},
+};
+use serde_json::{Value, json};
+
+/// Serves a peak, volcano or saddle node as a GeoJSON feature in EPSG:4326 with its isolation (in
+/// meters) and the prominence tier picking its symbol, to explain how it is rendered.
+pub async fn get(State(state): State<AppState>, Path(id): Path<i64>) -> Response<Body> {
+    if id <= 0 {
+        return error_response(StatusCode::BAD_REQUEST, "invalid node id");
+    }
+
+    let client = match state.db_pool.get().await {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("Peak {id} connection failed: {err}");
+
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "database unavailable");
+        }
+    };
+
+    let sql = format!(
+        "
+        SELECT
+            type,
+            name,
+            tags->'ele' AS ele,
+            isolation,
+            {PEAK_TIER_SQL} AS tier,
+            ST_AsGeoJSON(ST_Transform(geometry, 4326)) AS geometry
+        FROM
+            osm_pois
+        NATURAL LEFT JOIN
+            isolations
+        WHERE
+            osm_id = $1 AND
+            type IN ('peak', 'volcano', 'saddle', 'mountain_pass')
+    "
+    );
+
+    let row = match client.query_opt(&sql, &[&id]).await {
+        Ok(Some(row)) => row,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "peak not found"),
+        Err(err) => {
+            eprintln!("Peak {id} query failed: {err}");
+
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "query error");
+        }
+    };
+
+    let geometry: Value = match serde_json::from_str(row.get("geometry")) {
+        Ok(geometry) => geometry,
+        Err(err) => {
+            eprintln!("Peak {id} geometry parsing failed: {err}");
+
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "invalid geometry");
+        }
+    };
+
+    let typ: &str = row.get("type");
+
+    let feature = json!({
+        "type": "Feature",
+        "id": id,
+        "properties": {
+            "type": typ,
+            "name": row.get::<_, &str>("name"),
+            "ele": row.get::<_, Option<&str>>("ele"),
+            "isolation": row.get::<_, Option<f32>>("isolation"),
+            // saddles have no prominence tiers
+            "tier": matches!(typ, "peak" | "volcano").then(|| row.get::<_, &str>("tier")),
+        },
+        "geometry": geometry,
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "application/geo+json")
+        .body(Body::from(feature.to_string()))
+        .expect("body should be built")
+}
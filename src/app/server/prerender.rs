@@ -0,0 +1,225 @@
+use crate::{
+    app::{
+        server::{
+            app_state::AppState,
+            export_route::bbox4326_to_3857,
+            tile_route::{serve_tile, tile_bounds_to_epsg3857},
+        },
+        tile_coord::TileCoord,
+        tile_processor::cached_tile_path,
+    },
+    render::{TileCoverageRelation, tile_touches_coverage},
+};
+use axum::http::HeaderMap;
+use geo::{BoundingRect, Rect};
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::broadcast::Receiver,
+    time::{MissedTickBehavior, interval},
+};
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct PrerenderOptions {
+    pub zooms: RangeInclusive<u8>,
+    /// `[min_lon, min_lat, max_lon, max_lat]`; falls back to the coverage extent of the variant.
+    pub bbox: Option<[f64; 4]>,
+    /// Maximum number of tiles rendered per second.
+    pub rate: f64,
+}
+
+/// Renders and caches all tiles of the cached tile variants within the bbox at the configured
+/// zooms. Tiles are rendered one at a time and throttled to leave the worker pool to live
+/// traffic. Already cached tiles are skipped, so an interrupted run continues where it stopped.
+pub async fn run(state: AppState, options: PrerenderOptions, mut shutdown_rx: Receiver<()>) {
+    if state.tile_worker.is_none() {
+        eprintln!("Prerender disabled: missing --tile-cache-base-path");
+        return;
+    }
+
+    let mut jobs = Vec::new();
+
+    for (variant_index, variant) in state.tile_variants.iter().enumerate() {
+        let Some(ref tile_cache_base_path) = variant.tile_cache_base_path else {
+            continue;
+        };
+
+        let bbox = options.bbox.map(bbox4326_to_3857).map_or_else(
+            || {
+                variant
+                    .coverage_geometry
+                    .as_ref()
+                    .and_then(|coverage| coverage.bounding_rect())
+            },
+            |[min_x, min_y, max_x, max_y]| Some(Rect::new((min_x, min_y), (max_x, max_y))),
+        );
+
+        let Some(bbox) = bbox else {
+            eprintln!(
+                "Prerender of {} skipped: no --prerender-bbox nor coverage",
+                tile_cache_base_path.display()
+            );
+            continue;
+        };
+
+        jobs.push((variant_index, tile_cache_base_path, bbox));
+    }
+
+    let zooms = || options.zooms.clone();
+
+    let total: u64 = jobs
+        .iter()
+        .flat_map(|(_, _, bbox)| zooms().map(|zoom| tile_count(*bbox, zoom)))
+        .sum::<u64>()
+        * state.allowed_scales.len() as u64;
+
+    println!("Prerendering up to {total} tiles");
+
+    let mut ticker = interval(Duration::from_secs_f64(1.0 / options.rate));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut visited = 0u64;
+    let mut rendered = 0u64;
+    let mut failed = 0u64;
+    let mut last_progress = Instant::now();
+
+    for (variant_index, tile_cache_base_path, bbox) in jobs {
+        let variant = &state.tile_variants[variant_index];
+
+        for zoom in zooms().filter(|zoom| *zoom <= variant.max_zoom) {
+            let (xs, ys) = tile_range(bbox, zoom);
+
+            for x in xs {
+                for y in ys.clone() {
+                    let coord = TileCoord { zoom, x, y };
+
+                    if let Some(ref coverage_geometry) = variant.coverage_geometry {
+                        let tile_bbox = tile_bounds_to_epsg3857(x, y, zoom, 256);
+
+                        if tile_touches_coverage(
+                            coverage_geometry,
+                            tile_bbox,
+                            tile_bbox.width() / 256.0,
+                        ) == TileCoverageRelation::Outside
+                        {
+                            visited += state.allowed_scales.len() as u64;
+                            continue;
+                        }
+                    }
+
+                    for &scale in &state.allowed_scales {
+                        visited += 1;
+
+                        let file_path = cached_tile_path(
+                            tile_cache_base_path,
+                            coord,
+                            scale,
+                            state.cache_dir_hash_depth,
+                        );
+
+                        if tokio::fs::try_exists(&file_path).await.unwrap_or(false) {
+                            continue;
+                        }
+
+                        tokio::select! {
+                            _ = ticker.tick() => {},
+                            _ = shutdown_rx.recv() => {
+                                println!("Prerender stopped at {coord}@{scale}");
+                                return;
+                            }
+                        }
+
+                        let response = serve_tile(
+                            &state,
+                            variant_index,
+                            coord,
+                            scale,
+                            None,
+                            false,
+                            None,
+                            HeaderMap::new(),
+                        )
+                        .await;
+
+                        if response.status().is_success() {
+                            rendered += 1;
+                        } else {
+                            failed += 1;
+                        }
+
+                        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                            last_progress = Instant::now();
+
+                            println!(
+                                "Prerender progress: {visited}/{total} tiles, {rendered} rendered, {failed} failed"
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    println!("Prerender finished: {visited} tiles, {rendered} rendered, {failed} failed");
+}
+
+/// Inclusive x and y ranges of the tiles at `zoom` intersecting the EPSG:3857 bbox.
+fn tile_range(bbox: Rect<f64>, zoom: u8) -> (RangeInclusive<u32>, RangeInclusive<u32>) {
+    const HALF_CIRCUMFERENCE: f64 = std::f64::consts::PI * 6_378_137.0;
+
+    let tiles = 1u32 << zoom;
+
+    let to_tile = |meters: f64| {
+        ((meters + HALF_CIRCUMFERENCE) / (2.0 * HALF_CIRCUMFERENCE) * tiles as f64)
+            .floor()
+            .clamp(0.0, (tiles - 1) as f64) as u32
+    };
+
+    let min = bbox.min();
+    let max = bbox.max();
+
+    (
+        to_tile(min.x)..=to_tile(max.x),
+        (tiles - 1 - to_tile(max.y))..=(tiles - 1 - to_tile(min.y)),
+    )
+}
+
+fn tile_count(bbox: Rect<f64>, zoom: u8) -> u64 {
+    let (xs, ys) = tile_range(bbox, zoom);
+
+    u64::from(xs.end() - xs.start() + 1) * u64::from(ys.end() - ys.start() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::Coord;
+
+    fn shrunk(rect: Rect<f64>) -> Rect<f64> {
+        Rect::new(
+            rect.min() + Coord { x: 1.0, y: 1.0 },
+            rect.max() - Coord { x: 1.0, y: 1.0 },
+        )
+    }
+
+    #[test]
+    fn world_covers_all_tiles() {
+        let world = tile_bounds_to_epsg3857(0, 0, 0, 256);
+
+        assert_eq!(tile_range(world, 0), (0..=0, 0..=0));
+        assert_eq!(tile_range(world, 2), (0..=3, 0..=3));
+        assert_eq!(tile_count(world, 3), 64);
+    }
+
+    #[test]
+    fn tile_bbox_maps_back_to_the_tile_and_its_children() {
+        let bbox = shrunk(tile_bounds_to_epsg3857(141, 88, 8, 256));
+
+        assert_eq!(tile_range(bbox, 8), (141..=141, 88..=88));
+        assert_eq!(tile_range(bbox, 9), (282..=283, 176..=177));
+        assert_eq!(tile_count(bbox, 10), 16);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    app::server::{app_state::AppState, error_response::error_response},
+    render::RELATION_ID_OFFSET,
+};
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{Response, StatusCode},
+};
+use serde_json::{Value, json};
+
+/// Serves the ways of a route relation merged into a single GeoJSON `LineString` in EPSG:4326,
+/// e.g. for computing an elevation profile. Routes with gaps or branches are rejected as they
+/// can't be merged into one line.
+pub async fn get(State(state): State<AppState>, Path(id): Path<i64>) -> Response<Body> {
+    if id <= 0 {
+        return error_response(StatusCode::BAD_REQUEST, "invalid relation id");
+    }
+
+    let client = match state.db_pool.get().await {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("Route {id} geometry connection failed: {err}");
+
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "database unavailable");
+        }
+    };
+
+    let sql = "
+        SELECT
+            osm_routes.type,
+            osm_routes.name,
+            osm_routes.ref,
+            ST_AsGeoJSON(
+                ST_Transform(ST_LineMerge(ST_Collect(osm_route_members.geometry)), 4326)
+            ) AS geometry
+        FROM
+            osm_routes
+        JOIN
+            osm_route_members
+        ON
+            osm_route_members.osm_id = osm_routes.osm_id
+        WHERE
+            osm_routes.osm_id = $1 AND
+            GeometryType(osm_route_members.geometry) = 'LINESTRING'
+        GROUP BY
+            osm_routes.osm_id,
+            osm_routes.type,
+            osm_routes.name,
+            osm_routes.ref
+    ";
+
+    let row = match client.query_opt(sql, &[&(RELATION_ID_OFFSET - id)]).await {
+        Ok(Some(row)) => row,
+        Ok(None) => return error_response(StatusCode::NOT_FOUND, "route not found"),
+        Err(err) => {
+            eprintln!("Route {id} geometry query failed: {err}");
+
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "query error");
+        }
+    };
+
+    let geometry: Value = match serde_json::from_str(row.get("geometry")) {
+        Ok(geometry) => geometry,
+        Err(err) => {
+            eprintln!("Route {id} geometry parsing failed: {err}");
+
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, "invalid geometry");
+        }
+    };
+
+    if geometry["type"] != "LineString" {
+        return error_response(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "route ways don't merge into a single line",
+        );
+    }
+
+    let feature = json!({
+        "type": "Feature",
+        "id": id,
+        "properties": {
+            "type": row.get::<_, &str>("type"),
+            "name": row.get::<_, &str>("name"),
+            "ref": row.get::<_, &str>("ref"),
+        },
+        "geometry": geometry,
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "application/geo+json")
+        .body(Body::from(feature.to_string()))
+        .expect("body should be built")
+}
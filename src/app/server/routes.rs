@@ -3,17 +3,24 @@ use crate::{
         server::{
             app_state::{AppState, TileRouteState, TileVariantState},
             export_route::{self, ExportState},
-            legend_route, tile_route, wmts_route,
+            health_route::{self, HealthState},
+            http_server::{self, HttpOptions},
+            layer_features_route, legend_route, peak_info_route,
+            prerender::{self, PrerenderOptions},
+            route_geometry_route,
+            tile_route::{self, TileNotFoundBehavior},
+            tilejson_route, wmts_route,
         },
         tile_processing_worker::TileProcessingWorker,
     },
-    render::{RenderLayer, RenderWorkerPool},
+    render::{PoiFilter, RenderLayer, RenderWorkerPool},
 };
 use axum::{
     Router,
+    http::HeaderValue,
     routing::{get, post},
-    serve,
 };
+use deadpool_postgres::Pool;
 use geo::Geometry;
 use std::{
     io,
@@ -23,20 +30,33 @@ use std::{
 };
 use tokio::sync::broadcast::Receiver;
 use tower::limit::ConcurrencyLimitLayer;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
 pub struct ServerOptions {
     pub serve_cached: bool,
+    pub cache_dir_hash_depth: u8,
+    pub max_highlight_features: usize,
     pub max_zoom: u8,
     pub allowed_scales: Vec<f64>,
+    pub default_scale: f64,
+    pub dpr_hint: bool,
+    pub debug_headers: bool,
+    pub debug_endpoints: bool,
+    pub health_include_render: bool,
+    pub db_pool: Pool,
+    pub tile_not_found_behavior: TileNotFoundBehavior,
     pub max_concurrent_connections: usize,
+    pub http: HttpOptions,
     pub host: Ipv4Addr,
     pub port: u16,
     pub cors: bool,
+    pub cors_allow_origins: Vec<HeaderValue>,
     pub tile_variants: Vec<TileVariantOptions>,
     pub max_export_pixels: u64,
+    pub max_image_pixels: u64,
     pub max_parallel_exports: usize,
     pub export_abandon_grace: std::time::Duration,
+    pub prerender: Option<PrerenderOptions>,
 }
 
 pub struct TileVariantOptions {
@@ -44,6 +64,9 @@ pub struct TileVariantOptions {
     pub tile_cache_base_path: Option<PathBuf>,
     pub render: std::collections::HashSet<RenderLayer>,
     pub coverage_geometry: Option<Geometry>,
+    pub max_zoom: Option<u8>,
+    pub poi_filter: Option<PoiFilter>,
+    pub cache_control: Option<String>,
 }
 
 pub async fn start_server(
@@ -56,9 +79,17 @@ pub async fn start_server(
         .tile_variants
         .iter()
         .map(|variant| TileVariantState {
+            url_path: if variant.url_path == "/" {
+                String::new()
+            } else {
+                variant.url_path.clone()
+            },
             tile_cache_base_path: variant.tile_cache_base_path.clone(),
             coverage_geometry: variant.coverage_geometry.clone().map(Arc::new),
             render: variant.render.iter().copied().collect(),
+            max_zoom: variant.max_zoom.unwrap_or(options.max_zoom),
+            poi_filter: variant.poi_filter.clone().map(Arc::new),
+            cache_control: variant.cache_control.clone(),
         })
         .collect();
 
@@ -72,17 +103,34 @@ pub async fn start_server(
         export_state: Arc::new(ExportState::new(
             options.max_parallel_exports,
             options.max_export_pixels,
+            options.max_image_pixels,
             options.export_abandon_grace,
         )),
+        health_state: Arc::new(HealthState::new(options.health_include_render)),
         tile_variants: Arc::new(tile_variants),
         default_render,
         tile_worker,
         serve_cached: options.serve_cached,
-        max_zoom: options.max_zoom,
+        cache_dir_hash_depth: options.cache_dir_hash_depth,
+        max_highlight_features: options.max_highlight_features,
         allowed_scales: options.allowed_scales.clone(),
+        default_scale: options.default_scale,
+        dpr_hint: options.dpr_hint,
+        debug_headers: options.debug_headers,
+        db_pool: options.db_pool,
+        tile_not_found_behavior: options.tile_not_found_behavior,
     };
 
+    if let Some(prerender_options) = options.prerender {
+        tokio::spawn(prerender::run(
+            app_state.clone(),
+            prerender_options,
+            shutdown_rx.resubscribe(),
+        ));
+    }
+
     let mut router = Router::new()
+        .route("/healthz", get(health_route::get))
         .route("/service", get(wmts_route::service_handler))
         .route(
             "/export",
@@ -94,23 +142,31 @@ pub async fn start_server(
         .route("/legend", get(legend_route::get_metadata))
         .route("/legend/{id}", get(legend_route::get));
 
-    for (variant_index, variant) in options.tile_variants.iter().enumerate() {
-        let route_path = format!(
-            "{}/{{zoom}}/{{x}}/{{y}}",
-            if variant.url_path == "/" {
-                ""
-            } else {
-                &variant.url_path
-            }
-        );
+    if options.debug_endpoints {
+        router = router
+            .route("/debug/routes/{id}", get(route_geometry_route::get))
+            .route("/debug/peaks/{id}", get(peak_info_route::get))
+            .route(
+                "/debug/{zoom}/{x}/{y}/{layer}",
+                get(layer_features_route::get),
+            );
+    }
 
-        router = router.route(
-            &route_path,
-            get(tile_route::get).with_state(TileRouteState {
-                app_state: app_state.clone(),
-                variant_index,
-            }),
-        );
+    for (variant_index, variant) in app_state.tile_variants.iter().enumerate() {
+        let tile_route_state = TileRouteState {
+            app_state: app_state.clone(),
+            variant_index,
+        };
+
+        router = router
+            .route(
+                &format!("{}/{{zoom}}/{{x}}/{{y}}", variant.url_path),
+                get(tile_route::get).with_state(tile_route_state.clone()),
+            )
+            .route(
+                &format!("{}/tiles.json", variant.url_path),
+                get(tilejson_route::get).with_state(tile_route_state),
+            );
     }
 
     let mut router = router.with_state(app_state);
@@ -122,6 +178,13 @@ pub async fn start_server(
                 .allow_methods(Any)
                 .allow_headers(Any),
         );
+    } else if !options.cors_allow_origins.is_empty() {
+        router = router.layer(
+            CorsLayer::new()
+                .allow_origin(AllowOrigin::list(options.cors_allow_origins))
+                .allow_methods(Any)
+                .allow_headers(Any),
+        );
     }
 
     router = router.layer(ConcurrencyLimitLayer::new(
@@ -131,9 +194,8 @@ pub async fn start_server(
     let listener =
         tokio::net::TcpListener::bind(SocketAddr::from((options.host, options.port))).await?;
 
-    serve(listener, router)
-        .with_graceful_shutdown(async move {
-            let _ = shutdown_rx.recv().await;
-        })
-        .await
+    http_server::serve(listener, router, &options.http, async move {
+        let _ = shutdown_rx.recv().await;
+    })
+    .await
 }
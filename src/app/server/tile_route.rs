@@ -4,22 +4,39 @@ use crate::{
         tile_coord::TileCoord,
         tile_processor::cached_tile_path,
     },
-    render::{ImageFormat, RenderRequest, TileCoverageRelation, tile_touches_coverage},
+    render::{Highlight, ImageFormat, RenderRequest, TileCoverageRelation, tile_touches_coverage},
 };
 use axum::{
     body::{Body, Bytes},
     extract::{Path, Query, State},
-    http::{HeaderMap, Response, StatusCode, header},
+    http::{HeaderMap, HeaderValue, Response, StatusCode, header, response::Builder},
 };
+use clap::ValueEnum;
 use geo::Rect;
 use httpdate::parse_http_date;
-use image::{ColorType, codecs::jpeg::JpegEncoder};
-use std::{os::unix::fs::MetadataExt, sync::LazyLock, time::SystemTime};
+use image::{
+    ColorType, ImageEncoder,
+    codecs::{jpeg::JpegEncoder, png::PngEncoder},
+};
+use std::{
+    os::unix::fs::MetadataExt,
+    sync::LazyLock,
+    time::{Duration, Instant, SystemTime},
+};
 use tokio::{
     fs,
     io::{self, AsyncReadExt},
 };
 
+const DEFAULT_CACHE_CONTROL: &str = "no-cache";
+
+/// Out-of-coverage tiles never change, so they can be cached for a week.
+const GRAY_TILE_CACHE_CONTROL: &str = "public, max-age=604800";
+
+/// Placeholder tiles of `--tile-not-found-behavior` are cached briefly only, as raising the max
+/// zoom or allowing more scales turns them into real tiles.
+const PLACEHOLDER_TILE_CACHE_CONTROL: &str = "public, max-age=300";
+
 static GRAY_TILE_JPEG: LazyLock<Vec<u8>> = LazyLock::new(|| {
     const TILE_SIZE: usize = 256;
     const RED: u8 = 209;
@@ -48,15 +65,48 @@ static GRAY_TILE_JPEG: LazyLock<Vec<u8>> = LazyLock::new(|| {
     encoded
 });
 
+static TRANSPARENT_TILE_PNG: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    const TILE_SIZE: u32 = 256;
+
+    let mut encoded = Vec::new();
+
+    PngEncoder::new(&mut encoded)
+        .write_image(
+            &vec![0; (TILE_SIZE * TILE_SIZE * 4) as usize],
+            TILE_SIZE,
+            TILE_SIZE,
+            ColorType::Rgba8.into(),
+        )
+        .expect("encode transparent tile png");
+
+    encoded
+});
+
+/// Response to tiles above the max zoom or of a disallowed scale.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum TileNotFoundBehavior {
+    /// `404 Not Found`
+    #[default]
+    NotFound,
+    /// Transparent PNG tile
+    Transparent,
+    /// Gray JPEG tile, as outside of the coverage
+    Gray,
+}
+
 #[derive(serde::Deserialize)]
 pub struct QueryParams {
     rerender: Option<bool>,
+    highlight: Option<String>,
 }
 
 pub async fn get(
     State(tile_route_state): State<TileRouteState>,
     Path((zoom, x, y_with_suffix)): Path<(u8, u32, String)>,
-    Query(QueryParams { rerender }): Query<QueryParams>,
+    Query(QueryParams {
+        rerender,
+        highlight,
+    }): Query<QueryParams>,
     headers: HeaderMap,
 ) -> Response<Body> {
     let state = tile_route_state.app_state;
@@ -69,16 +119,97 @@ pub async fn get(
             .expect("body should be built");
     };
 
-    serve_tile(
+    // An explicit `@<scale>x` suffix always wins over the DPR client hint.
+    let use_dpr_hint = state.dpr_hint && scale.is_none();
+
+    let dpr_scale = if use_dpr_hint {
+        parse_dpr(&headers).and_then(|dpr| scale_for_dpr(dpr, &state.allowed_scales))
+    } else {
+        None
+    };
+
+    let highlight = match highlight.map(|highlight| highlight.parse::<Highlight>()) {
+        None => None,
+        Some(Err(err)) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(err))
+                .expect("body should be built");
+        }
+        Some(Ok(highlight)) if highlight.0.len() > state.max_highlight_features => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(format!(
+                    "at most {} features can be highlighted",
+                    state.max_highlight_features
+                )))
+                .expect("body should be built");
+        }
+        Some(Ok(highlight)) => Some(highlight),
+    };
+
+    let mut response = serve_tile(
         &state,
         variant_index,
         TileCoord { zoom, x, y },
-        scale,
+        scale.or(dpr_scale).unwrap_or(state.default_scale),
         ext,
         rerender.unwrap_or_default(),
+        highlight,
         headers,
     )
-    .await
+    .await;
+
+    if use_dpr_hint {
+        let response_headers = response.headers_mut();
+
+        response_headers.append(header::VARY, HeaderValue::from_static("Sec-CH-DPR, DPR"));
+        response_headers.insert("Accept-CH", HeaderValue::from_static("Sec-CH-DPR, DPR"));
+
+        if let Some(dpr_scale) = dpr_scale
+            && let Ok(value) = HeaderValue::from_str(&dpr_scale.to_string())
+        {
+            response_headers.insert("Content-DPR", value);
+        }
+    }
+
+    response
+}
+
+fn gray_tile_response(cache_control: &str) -> Response<Body> {
+    with_cache_headers(
+        Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "image/jpeg"),
+        cache_control,
+    )
+    .body(Body::from(Bytes::from_static(GRAY_TILE_JPEG.as_slice())))
+    .expect("body should be built")
+}
+
+/// Reads the device pixel ratio from the `Sec-CH-DPR` or legacy `DPR` client hint.
+fn parse_dpr(headers: &HeaderMap) -> Option<f64> {
+    ["Sec-CH-DPR", "DPR"].iter().find_map(|name| {
+        headers
+            .get(*name)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|dpr| dpr.is_finite() && *dpr > 0.0)
+    })
+}
+
+/// Picks the largest allowed scale not exceeding `dpr`, falling back to the smallest allowed
+/// one. `None` if no scale is allowed.
+fn scale_for_dpr(dpr: f64, allowed_scales: &[f64]) -> Option<f64> {
+    allowed_scales
+        .iter()
+        .copied()
+        .filter(|scale| *scale <= dpr + f64::EPSILON)
+        .reduce(f64::max)
+        .or_else(|| allowed_scales.iter().copied().reduce(f64::min))
 }
 
 pub async fn serve_tile(
@@ -88,6 +219,7 @@ pub async fn serve_tile(
     scale: f64,
     ext: Option<&str>,
     rerender: bool,
+    highlight: Option<Highlight>,
     headers: HeaderMap,
 ) -> Response<Body> {
     let Some(variant) = state.tile_variants.get(variant_index) else {
@@ -97,22 +229,29 @@ pub async fn serve_tile(
             .expect("body should be built");
     };
 
-    if coord.zoom > state.max_zoom {
-        return Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::empty())
-            .expect("body should be built");
-    }
-
-    if !state
-        .allowed_scales
-        .iter()
-        .any(|allowed| (*allowed - scale).abs() < f64::EPSILON)
+    if coord.zoom > variant.max_zoom
+        || !state
+            .allowed_scales
+            .iter()
+            .any(|allowed| (*allowed - scale).abs() < f64::EPSILON)
     {
-        return Response::builder()
-            .status(StatusCode::NOT_FOUND)
-            .body(Body::empty())
-            .expect("body should be built");
+        return match state.tile_not_found_behavior {
+            TileNotFoundBehavior::NotFound => Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::empty())
+                .expect("body should be built"),
+            TileNotFoundBehavior::Transparent => with_cache_headers(
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header("Content-Type", "image/png"),
+                PLACEHOLDER_TILE_CACHE_CONTROL,
+            )
+            .body(Body::from(Bytes::from_static(
+                TRANSPARENT_TILE_PNG.as_slice(),
+            )))
+            .expect("body should be built"),
+            TileNotFoundBehavior::Gray => gray_tile_response(PLACEHOLDER_TILE_CACHE_CONTROL),
+        };
     }
 
     let ext = ext.unwrap_or("jpeg");
@@ -126,21 +265,30 @@ pub async fn serve_tile(
 
     let bbox = tile_bounds_to_epsg3857(coord.x, coord.y, coord.zoom, 256);
 
+    let cache_control = variant
+        .cache_control
+        .as_deref()
+        .unwrap_or(DEFAULT_CACHE_CONTROL);
+
     if let Some(ref coverage_geometry) = variant.coverage_geometry {
         let meters_per_pixel = bbox.width() / 256.0;
         if tile_touches_coverage(coverage_geometry, bbox, meters_per_pixel)
             == TileCoverageRelation::Outside
         {
-            return Response::builder()
-                .status(StatusCode::OK)
-                .header("Content-Type", "image/jpeg")
-                .body(Body::from(Bytes::from_static(GRAY_TILE_JPEG.as_slice())))
-                .expect("body should be built");
+            return gray_tile_response(GRAY_TILE_CACHE_CONTROL);
         }
     }
 
-    let file_path = if let Some(ref tile_cache_base_path) = variant.tile_cache_base_path {
-        let file_path = cached_tile_path(tile_cache_base_path, coord, scale);
+    // Highlighted tiles are specific to the request, so they bypass the cache.
+    let file_path = if highlight.is_none()
+        && let Some(ref tile_cache_base_path) = variant.tile_cache_base_path
+    {
+        let file_path = cached_tile_path(
+            tile_cache_base_path,
+            coord,
+            scale,
+            state.cache_dir_hash_depth,
+        );
 
         enum ModifiedOrFresh {
             Modified(Vec<u8>, Option<SystemTime>),
@@ -157,6 +305,16 @@ pub async fn serve_tile(
 
                 let mtime = metadata.modified().ok();
 
+                // the file of an invalidated tile may not have been removed yet
+                if let Some(mtime) = mtime
+                    && let Some(tile_worker) = state.tile_worker.as_ref()
+                    && tile_worker
+                        .last_invalidation(coord)
+                        .is_some_and(|invalidated_at| invalidated_at >= mtime)
+                {
+                    return Err(io::ErrorKind::NotFound.into());
+                }
+
                 if let Some(ims) = headers.get(header::IF_MODIFIED_SINCE)
                     && let Ok(ims_time) = parse_http_date(ims.to_str().unwrap_or(""))
                     && let Some(mtime) = mtime
@@ -175,25 +333,36 @@ pub async fn serve_tile(
 
             match result {
                 Ok(ModifiedOrFresh::Modified(data, modified)) => {
-                    let mut builder = Response::builder()
-                        .status(StatusCode::OK)
-                        .header("Content-Type", "image/jpeg")
-                        .header("Cache-Control", "no-cache");
+                    let mut builder = with_cache_headers(
+                        Response::builder()
+                            .status(StatusCode::OK)
+                            .header("Content-Type", "image/jpeg"),
+                        cache_control,
+                    );
 
                     if let Some(modified) = modified {
                         builder =
                             builder.header("Last-Modified", httpdate::fmt_http_date(modified));
                     }
 
+                    if state.debug_headers {
+                        builder = builder.header("X-Tile-Cache", "hit");
+                    }
+
                     return builder.body(Body::from(data)).expect("cached body");
                 }
                 Ok(ModifiedOrFresh::Fresh(date)) => {
-                    return Response::builder()
-                        .status(StatusCode::NOT_MODIFIED)
-                        .header("Cache-Control", "no-cache")
-                        .header("Last-Modified", httpdate::fmt_http_date(date))
-                        .body(Body::empty())
-                        .expect("empty body");
+                    let mut builder = with_cache_headers(
+                        Response::builder().status(StatusCode::NOT_MODIFIED),
+                        cache_control,
+                    )
+                    .header("Last-Modified", httpdate::fmt_http_date(date));
+
+                    if state.debug_headers {
+                        builder = builder.header("X-Tile-Cache", "hit");
+                    }
+
+                    return builder.body(Body::empty()).expect("empty body");
                 }
                 Err(err) => {
                     if err.kind() != std::io::ErrorKind::NotFound {
@@ -210,7 +379,7 @@ pub async fn serve_tile(
 
     let render_started_at = SystemTime::now();
 
-    let render_request = RenderRequest::new(
+    let mut render_request = RenderRequest::new(
         bbox,
         coord.zoom,
         scale,
@@ -219,8 +388,13 @@ pub async fn serve_tile(
         variant.coverage_geometry.clone(),
     );
 
+    render_request.poi_filter = variant.poi_filter.clone();
+    render_request.highlight = highlight;
+
     // println!("{coord}");
 
+    let render_timer = Instant::now();
+
     let rendered = match state.render_worker_pool.render(render_request).await {
         Ok(rendered) => rendered,
         Err(err) => {
@@ -233,6 +407,8 @@ pub async fn serve_tile(
         }
     };
 
+    let render_time = render_timer.elapsed();
+
     if file_path.is_some()
         && let Some(tile_worker) = state.tile_worker.as_ref()
         && let Err(err) = tile_worker
@@ -248,18 +424,52 @@ pub async fn serve_tile(
         eprintln!("Enqueue tile {coord}@{scale} save failed: {err}");
     }
 
-    Response::builder()
-        .status(StatusCode::OK)
-        .header("Content-Type", "image/jpeg")
-        .header("Cache-Control", "no-cache")
-        .header("Last-Modified", httpdate::fmt_http_date(render_started_at))
+    let mut builder = with_cache_headers(
+        Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "image/jpeg"),
+        cache_control,
+    )
+    .header("Last-Modified", httpdate::fmt_http_date(render_started_at));
+
+    if state.debug_headers {
+        builder = builder
+            .header("X-Tile-Cache", "miss")
+            .header("X-Tile-Render-Time-Ms", render_time.as_millis().to_string());
+    }
+
+    builder
         .body(Body::from(rendered))
         .expect("body should be built")
 }
 
-fn parse_y_suffix(input: &str) -> Option<(u32, f64, Option<&str>)> {
+/// Sets `Cache-Control` and, if it carries a `max-age`, the matching `Expires` for HTTP/1.0 caches.
+fn with_cache_headers(builder: Builder, cache_control: &str) -> Builder {
+    let builder = builder.header(header::CACHE_CONTROL, cache_control);
+
+    match max_age(cache_control) {
+        Some(max_age) => builder.header(
+            header::EXPIRES,
+            httpdate::fmt_http_date(SystemTime::now() + max_age),
+        ),
+        None => builder,
+    }
+}
+
+fn max_age(cache_control: &str) -> Option<Duration> {
+    cache_control.split(',').find_map(|directive| {
+        let (name, value) = directive.split_once('=')?;
+
+        name.trim()
+            .eq_ignore_ascii_case("max-age")
+            .then(|| value.trim().parse().ok().map(Duration::from_secs))?
+    })
+}
+
+/// Parses `<y>[@<scale>x][.<ext>]`; the scale is `None` without the `@<scale>x` suffix.
+fn parse_y_suffix(input: &str) -> Option<(u32, Option<f64>, Option<&str>)> {
     let mut y_part = input;
-    let mut scale = 1.0;
+    let mut scale = None;
     let mut ext = None;
 
     if let Some((left, right)) = input.split_once('@') {
@@ -267,7 +477,7 @@ fn parse_y_suffix(input: &str) -> Option<(u32, f64, Option<&str>)> {
 
         let (scale_str, rest) = right.split_once('x')?;
 
-        scale = scale_str.parse::<f64>().ok()?;
+        scale = Some(scale_str.parse::<f64>().ok()?);
 
         if let Some(after_dot) = rest.strip_prefix('.') {
             if after_dot.is_empty() {
@@ -307,3 +517,38 @@ pub fn tile_bounds_to_epsg3857(x: u32, y: u32, zoom: u8, tile_size: u32) -> Rect
 
     Rect::new((min_x, min_y), (max_x, max_y))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_age_is_parsed_from_cache_control() {
+        assert_eq!(
+            max_age("public, max-age=3600"),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(max_age("Max-Age = 60"), Some(Duration::from_secs(60)));
+        assert_eq!(max_age("no-cache"), None);
+        assert_eq!(max_age("public, s-maxage=60"), None);
+    }
+
+    #[test]
+    fn scale_is_only_parsed_from_explicit_suffix() {
+        assert_eq!(parse_y_suffix("88.jpeg"), Some((88, None, Some("jpeg"))));
+        assert_eq!(parse_y_suffix("88@2x"), Some((88, Some(2.0), None)));
+        assert_eq!(
+            parse_y_suffix("88@1x.png"),
+            Some((88, Some(1.0), Some("png")))
+        );
+        assert_eq!(parse_y_suffix("88@x"), None);
+    }
+
+    #[test]
+    fn dpr_picks_largest_allowed_scale_not_exceeding_it() {
+        assert_eq!(scale_for_dpr(2.5, &[1.0, 2.0, 3.0]), Some(2.0));
+        assert_eq!(scale_for_dpr(3.0, &[1.0, 2.0, 3.0]), Some(3.0));
+        assert_eq!(scale_for_dpr(1.0, &[2.0, 3.0]), Some(2.0));
+        assert_eq!(scale_for_dpr(1.0, &[]), None);
+    }
+}
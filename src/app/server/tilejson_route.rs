@@ -0,0 +1,61 @@
+use crate::{app::server::app_state::TileRouteState, render::scale_denominator};
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderMap, Response, StatusCode, header},
+};
+use serde_json::{Map, Value, json};
+
+/// Serves the TileJSON of the tile variant. Next to the standard fields it lists the scale
+/// denominators of the zoom levels (from 0 to `maxzoom`) for every allowed scale, keyed by the
+/// scale.
+pub async fn get(
+    State(TileRouteState {
+        app_state: state,
+        variant_index,
+    }): State<TileRouteState>,
+    headers: HeaderMap,
+) -> Response<Body> {
+    let variant = &state.tile_variants[variant_index];
+
+    // absolute URLs for the clients not resolving them against the TileJSON URL
+    let origin = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(|host| {
+            let proto = headers
+                .get("X-Forwarded-Proto")
+                .and_then(|proto| proto.to_str().ok())
+                .unwrap_or("http");
+
+            format!("{proto}://{host}")
+        })
+        .unwrap_or_default();
+
+    let scale_denominators: Map<String, Value> = state
+        .allowed_scales
+        .iter()
+        .map(|&scale| {
+            let denominators: Vec<f64> = (0..=variant.max_zoom)
+                .map(|zoom| scale_denominator(zoom, scale))
+                .collect();
+
+            (scale.to_string(), json!(denominators))
+        })
+        .collect();
+
+    let tilejson = json!({
+        "tilejson": "3.0.0",
+        "scheme": "xyz",
+        "tiles": [format!("{origin}{}/{{z}}/{{x}}/{{y}}", variant.url_path)],
+        "minzoom": 0,
+        "maxzoom": variant.max_zoom,
+        "scale_denominators": scale_denominators,
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", "application/json")
+        .body(Body::from(tilejson.to_string()))
+        .expect("body should be built")
+}
@@ -57,6 +57,7 @@ pub async fn service_handler(
                 scale,
                 Some(ext),
                 false,
+                None,
                 headers,
             )
             .await
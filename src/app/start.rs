@@ -1,12 +1,19 @@
 use crate::app::{
     cli::{Cli, TileVariantInput},
-    server::{ServerOptions, TileVariantOptions, start_server},
+    preview::render_preview,
+    server::{
+        HttpOptions, PrerenderOptions, ServerOptions, TileVariantOptions, load_tls, start_server,
+    },
     tile_invalidation,
     tile_processing_worker::TileProcessingWorker,
-    tile_processor::{TileProcessingConfig, VariantConfig},
+    tile_processor::{TileProcessingConfig, TileProcessor, VariantConfig},
 };
-use crate::render::{RenderConfig, RenderWorkerPool, set_fonts_path, set_mapping_path};
-use deadpool_postgres::Config;
+use crate::render::{
+    RenderConfig, RenderWorkerPool, set_font_families, set_fonts_path, set_mapping_path,
+    shadowed_svgs,
+};
+use axum::http::HeaderValue;
+use deadpool_postgres::{Config, Hook, HookError};
 use dotenvy::dotenv;
 use geo::{Coord, Geometry, MapCoordsInPlace};
 use geojson::GeoJson;
@@ -30,6 +37,7 @@ pub fn start() {
     let cli = Cli::parse_checked();
     set_mapping_path(cli.mapping_path.clone());
     set_fonts_path(cli.fonts_path.clone());
+    set_font_families(cli.font_family.clone(), cli.font_fallback.clone());
 
     let tile_variants = match build_tile_variants(&cli) {
         Ok(config) => config,
@@ -41,6 +49,37 @@ pub fn start() {
         Err(err) => panic!("invalid tile processing configuration: {err}"),
     };
 
+    if cli.rebuild_index {
+        let result = TileProcessor::new(TileProcessingConfig {
+            variants: tile_processing_variants,
+            invalidate_min_zoom: cli.invalidate_min_zoom,
+            cache_dir_hash_depth: cli.cache_dir_hash_depth,
+        })
+        .map_err(std::io::Error::from)
+        .and_then(|processor| processor.rebuild_indexes());
+
+        match result {
+            Ok(count) => println!("Rebuilt tile indexes with {count} tiles"),
+            Err(err) => {
+                eprintln!("Rebuilding tile indexes failed: {err}");
+                std::process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    let tls = match cli.tls_cert.as_deref().zip(cli.tls_key.as_deref()) {
+        Some((cert, key)) => match load_tls(cert, key, cli.http2) {
+            Ok(acceptor) => Some(acceptor),
+            Err(err) => {
+                eprintln!("Loading TLS failed: {err}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
     let rt = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()
@@ -48,36 +87,87 @@ pub fn start() {
 
     let handle = rt.handle().clone();
 
+    let pool = {
+        let mut cfg = Config::new();
+        cfg.url = Some(cli.database_url.clone());
+        cfg.pool = Some(deadpool_postgres::PoolConfig {
+            max_size: cli.pool_max_size as usize,
+            ..Default::default()
+        });
+
+        let mut builder = cfg
+            .builder(tokio_postgres::NoTls)
+            .expect("db pool config")
+            .runtime(deadpool_postgres::Runtime::Tokio1);
+
+        if let Some(init_sql) = cli.connection_init_sql.clone() {
+            builder = builder.post_create(Hook::async_fn(move |client, _metrics| {
+                let init_sql = init_sql.clone();
+
+                Box::pin(async move {
+                    client
+                        .batch_execute(&init_sql)
+                        .await
+                        .map_err(HookError::Backend)
+                })
+            }));
+        }
+
+        builder.build().expect("build db pool")
+    };
+
+    let default_scale = cli.effective_default_scale();
+
     let render_worker_pool = {
-        let pool = {
-            let mut cfg = Config::new();
-            cfg.url = Some(cli.database_url.clone());
-            cfg.pool = Some(deadpool_postgres::PoolConfig {
-                max_size: cli.pool_max_size as usize,
-                ..Default::default()
-            });
-            cfg.create_pool(
-                Some(deadpool_postgres::Runtime::Tokio1),
-                tokio_postgres::NoTls,
-            )
-            .expect("build db pool")
-        };
+        let svg_base_paths: Vec<_> = cli
+            .svg_overrides_path
+            .into_iter()
+            .chain([cli.svg_base_path])
+            .collect();
+
+        for path in shadowed_svgs(&svg_base_paths) {
+            println!("SVG override {} shadows the default", path.display());
+        }
 
         let render_config = Arc::new(RenderConfig {
-            svg_base_path: Arc::from(cli.svg_base_path),
+            svg_base_paths,
             hillshading_base_path: cli.hillshading_base_path,
             hillshading_hierarchy: cli.hillshading_hierarchy,
+            preload_hillshading: cli.preload_hillshading,
             contour_countries: cli.contour_countries,
+            contour_intervals: cli.contour_intervals.with_unit(cli.contour_unit),
+            landcover_min_areas: cli.landcover_min_areas.unwrap_or_default(),
+            disabled_layers: cli.disable_layer.into_iter().collect(),
+            retry_on_connection_error: cli.retry_on_connection_error,
         });
 
         Arc::new(RenderWorkerPool::new(
-            pool,
+            pool.clone(),
             handle,
             cli.worker_count,
+            cli.pin_workers,
             render_config,
         ))
     };
 
+    if let Some(out_dir) = &cli.preview {
+        let render = tile_variants
+            .first()
+            .map(|variant| variant.render.clone())
+            .unwrap_or_default();
+
+        let result = rt.block_on(render_preview(&render_worker_pool, &render, out_dir));
+
+        render_worker_pool.shutdown();
+
+        if let Err(err) = result {
+            eprintln!("Preview failed: {err}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
     let mut tile_processing_worker = None;
     let mut tile_invalidation_watcher = None;
 
@@ -88,6 +178,7 @@ pub fn start() {
         let processing_config = TileProcessingConfig {
             variants: tile_processing_variants,
             invalidate_min_zoom: cli.invalidate_min_zoom,
+            cache_dir_hash_depth: cli.cache_dir_hash_depth,
         };
 
         println!("Starting tile processing worker");
@@ -140,16 +231,46 @@ pub fn start() {
         shutdown_tx.subscribe(),
         ServerOptions {
             serve_cached: cli.serve_cached,
+            cache_dir_hash_depth: cli.cache_dir_hash_depth,
+            max_highlight_features: cli.max_highlight_features,
             max_zoom: cli.max_zoom,
             allowed_scales: cli.allowed_scales,
+            default_scale,
+            dpr_hint: cli.dpr_hint,
+            debug_headers: cli.debug_headers,
+            debug_endpoints: cli.debug_endpoints,
+            health_include_render: cli.health_include_render,
+            db_pool: pool,
+            tile_not_found_behavior: cli.tile_not_found_behavior,
             max_concurrent_connections: cli.max_concurrent_connections,
+            http: HttpOptions {
+                http2: cli.http2,
+                keep_alive: cli.keep_alive,
+                header_read_timeout: std::time::Duration::from_secs(cli.header_read_timeout_secs),
+                http2_keep_alive_interval: cli
+                    .http2_keep_alive_interval_secs
+                    .map(std::time::Duration::from_secs),
+                http2_max_concurrent_streams: cli.http2_max_concurrent_streams,
+                tls,
+            },
             host: cli.host,
             port: cli.port,
             cors: cli.cors,
+            cors_allow_origins: cli
+                .cors_allow_origin
+                .iter()
+                .map(|origin| HeaderValue::from_str(origin).expect("validated origin"))
+                .collect(),
             tile_variants,
             max_export_pixels: cli.max_export_pixels,
+            max_image_pixels: cli.max_image_pixels,
             max_parallel_exports: cli.max_parallel_exports,
             export_abandon_grace: std::time::Duration::from_secs(cli.export_abandon_grace_secs),
+            prerender: cli.prerender.map(|zooms| PrerenderOptions {
+                zooms: zooms.range(),
+                bbox: cli.prerender_bbox.map(|bbox| bbox.0),
+                rate: cli.prerender_rate,
+            }),
         },
     )) {
         eprintln!("Server stopped with error: {err}");
@@ -199,6 +320,9 @@ fn tile_variant_input_to_server_variant(
         tile_cache_base_path: variant.tile_cache_base_path,
         render: variant.render,
         coverage_geometry,
+        max_zoom: variant.max_zoom,
+        poi_filter: variant.poi_filter,
+        cache_control: variant.cache_control,
     })
 }
 
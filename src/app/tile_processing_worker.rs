@@ -1,6 +1,9 @@
 use crate::app::{
     tile_coord::TileCoord,
-    tile_processor::{TileProcessingConfig, TileProcessor},
+    tile_processor::{
+        InvalidationRegister, TileProcessingConfig, TileProcessor, last_invalidation,
+        record_invalidation,
+    },
 };
 use std::{
     sync::{Arc, Mutex},
@@ -27,6 +30,7 @@ pub struct TileProcessingWorker {
 struct TileProcessingInner {
     tx: Mutex<Option<mpsc::Sender<TileProcessingMessage>>>,
     handle: Mutex<Option<thread::JoinHandle<()>>>,
+    invalidation_register: InvalidationRegister,
 }
 
 enum TileProcessingMessage {
@@ -50,6 +54,8 @@ impl TileProcessingWorker {
         // TODO propagate error
         let mut processor = TileProcessor::new(config).expect("tile processor");
 
+        let invalidation_register = processor.invalidation_register();
+
         let handle = thread::Builder::new()
             .name("tile-processing-worker".to_string())
             .spawn(move || {
@@ -92,6 +98,7 @@ impl TileProcessingWorker {
             inner: Arc::new(TileProcessingInner {
                 tx: Mutex::new(Some(tx)),
                 handle: Mutex::new(Some(handle)),
+                invalidation_register,
             }),
         }
     }
@@ -130,6 +137,9 @@ impl TileProcessingWorker {
             guard.clone().ok_or(TileProcessingSendError::QueueClosed)?
         };
 
+        // recorded before the cached files get removed so that they aren't reported as fresh
+        record_invalidation(&self.inner.invalidation_register, coord, invalidated_at);
+
         tx.blocking_send(TileProcessingMessage::Invalidate {
             coord,
             invalidated_at,
@@ -137,6 +147,11 @@ impl TileProcessingWorker {
         .map_err(|_| TileProcessingSendError::QueueClosed)
     }
 
+    /// Latest invalidation of the tile or any of its ancestors within the register TTL.
+    pub(crate) fn last_invalidation(&self, coord: TileCoord) -> Option<SystemTime> {
+        last_invalidation(&self.inner.invalidation_register, coord)
+    }
+
     pub(crate) fn shutdown(&self) {
         let tx = self.inner.tx.lock().expect("mutex not poisoned").take();
         drop(tx);
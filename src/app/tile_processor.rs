@@ -3,10 +3,14 @@ use sled::Batch;
 use std::{
     collections::{HashMap, HashSet},
     fs, io,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
     time::{Duration, SystemTime},
 };
 
+/// Recent invalidation times of tiles, shared with the server to avoid stale `304`s.
+pub type InvalidationRegister = Arc<Mutex<HashMap<TileCoord, SystemTime>>>;
+
 #[derive(Clone)]
 pub struct VariantConfig {
     pub(crate) tile_cache_base_path: Option<PathBuf>,
@@ -17,6 +21,7 @@ pub struct VariantConfig {
 pub struct TileProcessingConfig {
     pub(crate) variants: Vec<VariantConfig>,
     pub(crate) invalidate_min_zoom: u8,
+    pub(crate) cache_dir_hash_depth: u8,
 }
 
 struct VariantRuntime {
@@ -27,7 +32,8 @@ struct VariantRuntime {
 pub struct TileProcessor {
     variants: Vec<VariantRuntime>,
     invalidate_min_zoom: u8,
-    invalidation_register: HashMap<TileCoord, SystemTime>,
+    cache_dir_hash_depth: u8,
+    invalidation_register: InvalidationRegister,
     last_prune: SystemTime,
 }
 
@@ -67,11 +73,16 @@ impl TileProcessor {
         Ok(Self {
             variants,
             invalidate_min_zoom: config.invalidate_min_zoom,
-            invalidation_register: HashMap::new(),
+            cache_dir_hash_depth: config.cache_dir_hash_depth,
+            invalidation_register: InvalidationRegister::default(),
             last_prune: SystemTime::now(),
         })
     }
 
+    pub(crate) fn invalidation_register(&self) -> InvalidationRegister {
+        self.invalidation_register.clone()
+    }
+
     pub(crate) const fn last_prune(&self) -> SystemTime {
         self.last_prune
     }
@@ -103,7 +114,12 @@ impl TileProcessor {
 
         Self::append_index_entry(variant.db.as_ref(), coord, scale);
 
-        let file_path = cached_tile_path(tile_cache_base_path, coord, scale);
+        let file_path = cached_tile_path(
+            tile_cache_base_path,
+            coord,
+            scale,
+            self.cache_dir_hash_depth,
+        );
 
         if let Some(parent) = file_path.parent()
             && let Err(err) = fs::create_dir_all(parent)
@@ -126,8 +142,8 @@ impl TileProcessor {
         }
     }
 
-    pub(crate) fn handle_invalidation(&mut self, coord: TileCoord, invalidated_at: SystemTime) {
-        self.record_invalidation(coord, invalidated_at);
+    pub(crate) fn handle_invalidation(&self, coord: TileCoord, invalidated_at: SystemTime) {
+        record_invalidation(&self.invalidation_register, coord, invalidated_at);
 
         for variant in &self.variants {
             let (Some(base_path), Some(db)) =
@@ -138,7 +154,7 @@ impl TileProcessor {
 
             let mut batch = Batch::default();
 
-            Self::remove_descendants(db, &mut batch, coord, base_path);
+            Self::remove_descendants(db, &mut batch, coord, base_path, self.cache_dir_hash_depth);
 
             let mut current = coord;
             loop {
@@ -152,7 +168,13 @@ impl TileProcessor {
 
                 current = parent;
 
-                Self::remove_exact(db, &mut batch, current, base_path);
+                Self::remove_exact(
+                    db,
+                    &mut batch,
+                    current,
+                    base_path,
+                    self.cache_dir_hash_depth,
+                );
             }
 
             if let Err(err) = db.apply_batch(batch) {
@@ -161,27 +183,48 @@ impl TileProcessor {
         }
     }
 
-    pub(crate) fn prune_invalidation_register(&mut self, now: SystemTime, ttl: Duration) {
+    pub(crate) fn prune_invalidation_register(&self, now: SystemTime, ttl: Duration) {
         self.invalidation_register
+            .lock()
+            .expect("mutex not poisoned")
             .retain(|_, ts| now.duration_since(*ts).unwrap_or(Duration::ZERO) <= ttl);
     }
 
-    fn record_invalidation(&mut self, coord: TileCoord, invalidated_at: SystemTime) {
-        let entry = self
-            .invalidation_register
-            .entry(coord)
-            .or_insert(invalidated_at);
+    /// Replaces the index of every variant having a cache with the tiles actually present in
+    /// its directory. Returns the number of indexed tiles.
+    pub(crate) fn rebuild_indexes(&self) -> io::Result<usize> {
+        let mut count = 0;
 
-        if *entry < invalidated_at {
-            *entry = invalidated_at;
+        for variant in &self.variants {
+            let (Some(base_path), Some(db)) =
+                (variant.tile_cache_base_path.as_ref(), variant.db.as_ref())
+            else {
+                continue;
+            };
+
+            db.clear()?;
+
+            walk_cached_tiles(base_path, self.cache_dir_hash_depth, &mut |coord, scale| {
+                Self::append_index_entry(Some(db), coord, scale);
+                count += 1;
+            })?;
+
+            db.flush()?;
         }
+
+        Ok(count)
     }
 
     fn should_drop_save(&self, coord: TileCoord, render_started_at: SystemTime) -> bool {
+        let register = self
+            .invalidation_register
+            .lock()
+            .expect("mutex not poisoned");
+
         let mut coord = coord;
 
         loop {
-            if let Some(invalidated_at) = self.invalidation_register.get(&coord)
+            if let Some(invalidated_at) = register.get(&coord)
                 && render_started_at >= *invalidated_at
             {
                 return true;
@@ -214,6 +257,7 @@ impl TileProcessor {
         batch: &mut Batch,
         coord: TileCoord,
         base_path: &std::path::Path,
+        hash_depth: u8,
     ) {
         let key: Vec<u8> = coord.into();
 
@@ -221,7 +265,7 @@ impl TileProcessor {
             match item {
                 Ok(entry) => {
                     let entry_coord = entry.0.as_ref().into();
-                    Self::remove_files(entry_coord, entry.1.as_ref(), base_path);
+                    Self::remove_files(entry_coord, entry.1.as_ref(), base_path, hash_depth);
                     batch.remove(entry.0);
                 }
                 Err(err) => {
@@ -236,6 +280,7 @@ impl TileProcessor {
         batch: &mut Batch,
         coord: TileCoord,
         base_path: &std::path::Path,
+        hash_depth: u8,
     ) {
         let key: Vec<u8> = coord.into();
 
@@ -248,15 +293,15 @@ impl TileProcessor {
             }
         };
 
-        Self::remove_files(coord, scales.as_ref(), base_path);
+        Self::remove_files(coord, scales.as_ref(), base_path, hash_depth);
         batch.remove(key);
     }
 
-    fn remove_files(coord: TileCoord, scales: &[u8], base_path: &std::path::Path) {
+    fn remove_files(coord: TileCoord, scales: &[u8], base_path: &std::path::Path, hash_depth: u8) {
         let unique_scales: HashSet<u8> = scales.iter().copied().collect();
 
         for scale in unique_scales {
-            let path = cached_tile_path(base_path, coord, scale as f64);
+            let path = cached_tile_path(base_path, coord, scale as f64, hash_depth);
 
             if let Err(err) = fs::remove_file(&path)
                 && err.kind() != io::ErrorKind::NotFound
@@ -267,10 +312,167 @@ impl TileProcessor {
     }
 }
 
-pub fn cached_tile_path(base: &std::path::Path, coord: TileCoord, scale: f64) -> PathBuf {
+/// Records the invalidation of the tile, keeping the latest time.
+pub(crate) fn record_invalidation(
+    register: &InvalidationRegister,
+    coord: TileCoord,
+    invalidated_at: SystemTime,
+) {
+    let mut register = register.lock().expect("mutex not poisoned");
+
+    let entry = register.entry(coord).or_insert(invalidated_at);
+
+    if *entry < invalidated_at {
+        *entry = invalidated_at;
+    }
+}
+
+/// Latest recorded invalidation of the tile or any of its ancestors.
+pub(crate) fn last_invalidation(
+    register: &InvalidationRegister,
+    coord: TileCoord,
+) -> Option<SystemTime> {
+    let register = register.lock().expect("mutex not poisoned");
+
+    let mut coord = coord;
+    let mut latest = register.get(&coord).copied();
+
+    while let Some(parent) = coord.parent() {
+        coord = parent;
+        latest = latest.max(register.get(&coord).copied());
+    }
+
+    latest
+}
+
+/// Path of a cached tile: `z/x/y@scale.jpeg`, with `hash_depth` extra directory levels between
+/// `x` and the file, each named by one byte of `y` (lowest first) in hex.
+pub fn cached_tile_path(
+    base: &std::path::Path,
+    coord: TileCoord,
+    scale: f64,
+    hash_depth: u8,
+) -> PathBuf {
     let mut path = base.to_owned();
     path.push(coord.zoom.to_string());
     path.push(coord.x.to_string());
+    for level in 0..u32::from(hash_depth.min(4)) {
+        path.push(format!("{:02x}", (coord.y >> (8 * level)) & 0xff));
+    }
     path.push(format!("{}@{scale}.jpeg", coord.y));
     path
 }
+
+/// Calls `f` for every tile found under `base` at the path [`cached_tile_path`] gives it.
+fn walk_cached_tiles(
+    base: &Path,
+    hash_depth: u8,
+    f: &mut impl FnMut(TileCoord, f64),
+) -> io::Result<()> {
+    fn parsed_entries<T: std::str::FromStr>(dir: &Path) -> io::Result<Vec<(T, PathBuf)>> {
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+
+            if let Some(value) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse().ok())
+            {
+                entries.push((value, entry.path()));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    if !base.is_dir() {
+        return Ok(());
+    }
+
+    for (zoom, zoom_dir) in parsed_entries::<u8>(base)? {
+        for (x, x_dir) in parsed_entries::<u32>(&zoom_dir)? {
+            let mut dirs = vec![x_dir];
+
+            for _ in 0..hash_depth.min(4) {
+                let mut subdirs = Vec::new();
+
+                for dir in dirs {
+                    for entry in fs::read_dir(dir)? {
+                        let entry = entry?;
+
+                        if entry.file_type()?.is_dir() {
+                            subdirs.push(entry.path());
+                        }
+                    }
+                }
+
+                dirs = subdirs;
+            }
+
+            for dir in dirs {
+                for entry in fs::read_dir(dir)? {
+                    let path = entry?.path();
+
+                    let Some((y, scale)) = path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .and_then(parse_tile_file_name)
+                    else {
+                        continue;
+                    };
+
+                    let coord = TileCoord { zoom, x, y };
+
+                    // skip files misplaced in the hash directories
+                    if cached_tile_path(base, coord, scale, hash_depth) == path {
+                        f(coord, scale);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses `y@scale.jpeg` of a cached tile file name.
+fn parse_tile_file_name(name: &str) -> Option<(u32, f64)> {
+    let (y, scale) = name.strip_suffix(".jpeg")?.split_once('@')?;
+
+    Some((y.parse().ok()?, scale.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tile_file_name_is_parsed() {
+        assert_eq!(parse_tile_file_name("88@2.jpeg"), Some((88, 2.0)));
+        assert_eq!(parse_tile_file_name("88@1.5.jpeg"), Some((88, 1.5)));
+        assert_eq!(parse_tile_file_name("88.jpeg"), None);
+        assert_eq!(parse_tile_file_name("88@2.jpeg.tmp"), None);
+    }
+
+    #[test]
+    fn parsed_file_name_matches_cached_tile_path() {
+        let coord = TileCoord {
+            zoom: 14,
+            x: 9000,
+            y: 5700,
+        };
+
+        let path = cached_tile_path(Path::new("/cache"), coord, 2.0, 2);
+
+        assert_eq!(path, Path::new("/cache/14/9000/44/16/5700@2.jpeg"));
+
+        assert_eq!(
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(parse_tile_file_name),
+            Some((5700, 2.0))
+        );
+    }
+}
@@ -165,10 +165,13 @@ pub const AEROWAY: Color = parse_color("hsl(260, 10%, 50%)");
 pub const ALLOTMENTS: Color = parse_color("hsl(50, 45%, 88%)");
 pub const AREA_LABEL: Color = parse_color("hsl(0, 0%, 33%)");
 pub const BEACH: Color = parse_color("hsl(60, 90%, 85%)");
+pub const BRIDGE_AREA_CASING: Color = parse_color("hsl(0, 0%, 35%)");
 pub const BROWNFIELD: Color = parse_color("hsl(30, 30%, 68%)");
 pub const BUILDING: Color = parse_color("hsl(0, 0%, 50%)");
 pub const BRIDLEWAY: Color = parse_color("hsl(120, 50%, 30%)");
 pub const BRIDLEWAY2: Color = parse_color("hsl(120, 50%, 80%)");
+pub const BRIDLEWAY_HARD: Color = parse_color("hsl(40, 80%, 75%)");
+pub const BRIDLEWAY_ROUGH: Color = parse_color("hsl(0, 70%, 75%)");
 pub const COLLEGE: Color = parse_color("hsl(60, 85%, 92%)");
 pub const COMMERCIAL: Color = parse_color("hsl(320, 40%, 90%)");
 pub const CONTOUR: Color = parse_color("hsl(0, 0%, 0%)");
@@ -184,7 +187,9 @@ pub const HEATH: Color = parse_color("hsl(85, 60%, 85%)");
 pub const HOSPITAL: Color = parse_color("hsl(50, 85%, 92%)");
 pub const INDUSTRIAL: Color = parse_color("hsl(0, 0%, 85%)");
 pub const LANDFILL: Color = parse_color("hsl(0, 30%, 75%)");
+pub const MARINA: Color = parse_color("hsl(216, 50%, 85%)");
 pub const MILITARY: Color = parse_color("hsl(0, 96%, 39%)");
+pub const MUD: Color = parse_color("hsl(30, 25%, 82%)");
 pub const NONE: Color = parse_color("hsl(0, 100%, 100%)");
 pub const ORCHARD: Color = parse_color("hsl(90, 75%, 85%)");
 pub const PARKING_STROKE: Color = parse_color("hsl(0, 30%, 75%)");
@@ -193,8 +198,14 @@ pub const PIER: Color = parse_color("hsl(0, 0%, 0%)");
 pub const PIPELINE: Color = parse_color("hsl(0, 0%, 50%)");
 pub const PISTE: Color = parse_color("hsl(0, 100%, 100%)");
 pub const PISTE2: Color = parse_color("hsl(0, 0%, 62%)");
+pub const PISTE_NORDIC: Color = parse_color("hsl(210, 80%, 40%)");
 pub const PITCH_STROKE: Color = parse_color("hsl(110, 35%, 50%)");
 pub const PITCH: Color = parse_color("hsl(110, 35%, 75%)");
+pub const GOLF_FAIRWAY: Color = parse_color("hsl(100, 50%, 72%)");
+pub const GOLF_GREEN: Color = parse_color("hsl(110, 60%, 62%)");
+pub const GOLF_STROKE: Color = parse_color("hsl(110, 45%, 40%)");
+pub const GOLF_BUNKER: Color = parse_color("hsl(45, 70%, 82%)");
+pub const GOLF_BUNKER_STROKE: Color = parse_color("hsl(40, 50%, 60%)");
 pub const POWER_LINE: Color = parse_color("hsl(0, 0%, 0%)");
 pub const POWER_LINE_MINOR: Color = parse_color("hsl(0, 0%, 50%)");
 pub const PROTECTED: Color = parse_color("hsl(120, 75%, 25%)");
@@ -203,16 +214,26 @@ pub const GLACIER: Color = parse_color("hsl(216, 65%, 90%)");
 pub const QUARRY: Color = parse_color("hsl(0, 0%, 78%)");
 pub const RESIDENTIAL: Color = parse_color("hsl(100, 0%, 91%)");
 pub const ROAD: Color = parse_color("hsl(40, 60%, 50%)");
+pub const SAC_SCALE_MOUNTAIN: Color = parse_color("hsl(50, 100%, 50%)");
+pub const SAC_SCALE_DEMANDING: Color = parse_color("hsl(30, 100%, 50%)");
+pub const SAC_SCALE_ALPINE: Color = parse_color("hsl(0, 100%, 45%)");
 pub const SCREE: Color = parse_color("hsl(0, 0%, 90%)");
 pub const SCRUB: Color = parse_color("hsl(100, 70%, 86%)");
+pub const SEAMARK_GREEN: Color = parse_color("hsl(120, 100%, 30%)");
+pub const SEAMARK_RED: Color = parse_color("hsl(0, 100%, 45%)");
+pub const SEAMARK_YELLOW: Color = parse_color("hsl(50, 100%, 50%)");
 pub const SILO_STROKE: Color = parse_color("hsl(50, 20%, 30%)");
 pub const SILO: Color = parse_color("hsl(50, 20%, 50%)");
 pub const SUPERROAD: Color = parse_color("hsl(10, 60%, 60%)");
+pub const TIDALFLAT: Color = parse_color("hsl(200, 25%, 82%)");
 pub const TRACK: Color = parse_color("hsl(0, 33%, 25%)");
 pub const WATER_LABEL_HALO: Color = parse_color("hsl(216, 30%, 100%)");
 pub const WATER_LABEL: Color = parse_color("hsl(216, 100%, 50%)");
 pub const WATER_SLIDE: Color = parse_color("hsl(180, 50%, 50%)");
+pub const ATTRACTION_TRACK: Color = parse_color("hsl(290, 60%, 40%)");
+pub const CONVEYING: Color = parse_color("hsl(200, 60%, 60%)");
 pub const WATER: Color = parse_color("hsl(216, 65%, 70%)");
+pub const WINTER_SPORTS_LABEL: Color = parse_color("hsl(216, 65%, 35%)");
 pub const RAIL_GLOW: Color = parse_color("hsl(0, 100%, 100%)");
 pub const TRAM: Color = parse_color("hsl(0, 0%, 20%)");
 pub const RAILWAY_DISUSED: Color = parse_color("hsl(0, 0%, 66%)");
@@ -228,6 +249,8 @@ pub const SOLAR_FG: Color = parse_color("hsl(250, 57%, 76%)");
 pub const TREE: Color = parse_color("hsl(120, 100%, 31%)");
 pub const DAM_LINE: Color = parse_color("hsl(0, 0%, 40%)");
 pub const SOLAR_PLANT_BORDER: Color = parse_color("hsl(250, 60%, 50%)");
+pub const HIGHLIGHT: Color = parse_color("hsl(300, 100%, 50%)");
+pub const WIKIDATA_LINKED: Color = parse_color("hsl(200, 100%, 40%)");
 
 pub trait ContextExt {
     fn set_source_color(&self, color: Color);
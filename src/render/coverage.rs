@@ -4,13 +4,15 @@ pub const MAX_EDGE_FADE_RADIUS_M: f64 = 5_000.0;
 pub const EDGE_FADE_CUTOFF_SIGMA: f64 = 3.0;
 pub const MAX_EDGE_FADE_SIGMA_PX: f64 = 10.0;
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum TileCoverageRelation {
     Inside,
     Crosses,
     Outside,
 }
 
+/// Relation of the tile (padded by the edge fade cutoff) to the coverage. Holes of the coverage
+/// polygons are outside the coverage, so a tile lying inside an excluded enclave is `Outside`.
 pub fn tile_touches_coverage(
     coverage: &Geometry,
     bbox: Rect<f64>,
@@ -52,3 +54,47 @@ fn edge_fade_cutoff_m(meters_per_pixel: f64) -> f64 {
 
     cutoff_from_data_m.min(cutoff_from_sigma_m)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{LineString, Polygon};
+
+    fn square(min: f64, max: f64) -> LineString {
+        LineString::from(vec![
+            (min, min),
+            (max, min),
+            (max, max),
+            (min, max),
+            (min, min),
+        ])
+    }
+
+    fn coverage_with_hole() -> Geometry {
+        Geometry::Polygon(Polygon::new(
+            square(0.0, 1_000_000.0),
+            vec![square(400_000.0, 600_000.0)],
+        ))
+    }
+
+    fn relation(min: f64) -> TileCoverageRelation {
+        let bbox = Rect::new((min, min), (min + 20_000.0, min + 20_000.0));
+
+        tile_touches_coverage(&coverage_with_hole(), bbox, bbox.width() / 256.0)
+    }
+
+    #[test]
+    fn tile_inside_hole_is_outside() {
+        assert_eq!(relation(490_000.0), TileCoverageRelation::Outside);
+    }
+
+    #[test]
+    fn tile_away_from_hole_is_inside() {
+        assert_eq!(relation(100_000.0), TileCoverageRelation::Inside);
+    }
+
+    #[test]
+    fn tile_on_hole_edge_crosses() {
+        assert_eq!(relation(395_000.0), TileCoverageRelation::Crosses);
+    }
+}
@@ -3,13 +3,26 @@ use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use cairo::Context;
-use cosmic_text::{FontSystem, fontdb};
+use cosmic_text::{Fallback, Family, FontSystem, PlatformFallback, fontdb};
 use swash::scale::ScaleContext;
 use swash::zeno::Verb;
 use swash::{FontRef, scale::outline::Outline};
+use unicode_script::Script;
 
 static FONTS_PATH: OnceLock<PathBuf> = OnceLock::new();
 
+static FONT_FAMILIES: OnceLock<FontFamilies> = OnceLock::new();
+
+const DEFAULT_FONT_FAMILY: &str = "PT Sans";
+
+const DEFAULT_NARROW_FONT_FAMILY: &str = "PT Sans Narrow";
+
+struct FontFamilies {
+    regular: &'static str,
+    narrow: &'static str,
+    fallback: Vec<&'static str>,
+}
+
 pub fn set_fonts_path(path: PathBuf) {
     FONTS_PATH
         .set(path)
@@ -23,10 +36,102 @@ fn configured_fonts_path() -> &'static Path {
         .expect("fonts path not configured; call set_fonts_path() at startup")
 }
 
-fn build_font_system(fonts_dir: &Path) -> FontSystem {
+/// Sets the label font family and the families tried for characters it lacks. The narrow
+/// variant is `<family> Narrow` if available. Families missing in the fonts path are reported.
+pub fn set_font_families(family: String, fallback: Vec<String>) {
+    let db = load_fonts_db(configured_fonts_path());
+
+    let has_family = |name: &str| {
+        db.faces()
+            .any(|face| face.families.iter().any(|(family, _)| family == name))
+    };
+
+    for name in std::iter::once(&family).chain(&fallback) {
+        if !has_family(name) {
+            eprintln!("Font family {name} not found in the fonts path");
+        }
+    }
+
+    let narrow = format!("{family} Narrow");
+
+    let narrow = if has_family(&narrow) {
+        narrow.leak()
+    } else {
+        family.clone().leak()
+    };
+
+    let font_families = FontFamilies {
+        regular: family.leak(),
+        narrow,
+        fallback: fallback.into_iter().map(|name| &*name.leak()).collect(),
+    };
+
+    assert!(
+        FONT_FAMILIES.set(font_families).is_ok(),
+        "font families already configured"
+    );
+}
+
+fn configured_font_families() -> &'static FontFamilies {
+    FONT_FAMILIES.get_or_init(|| FontFamilies {
+        regular: DEFAULT_FONT_FAMILY,
+        narrow: DEFAULT_NARROW_FONT_FAMILY,
+        fallback: Vec::new(),
+    })
+}
+
+/// Family of label text, optionally its narrow variant.
+pub fn font_family(narrow: bool) -> Family<'static> {
+    let families = configured_font_families();
+
+    Family::Name(if narrow {
+        families.narrow
+    } else {
+        families.regular
+    })
+}
+
+/// Tries the configured fallback families before the platform ones.
+struct ConfiguredFallback {
+    common: Vec<&'static str>,
+    platform: PlatformFallback,
+}
+
+impl Fallback for ConfiguredFallback {
+    fn common_fallback(&self) -> &[&'static str] {
+        &self.common
+    }
+
+    fn forbidden_fallback(&self) -> &[&'static str] {
+        self.platform.forbidden_fallback()
+    }
+
+    fn script_fallback(&self, script: Script, locale: &str) -> &[&'static str] {
+        self.platform.script_fallback(script, locale)
+    }
+}
+
+fn load_fonts_db(fonts_dir: &Path) -> fontdb::Database {
     let mut db = fontdb::Database::new();
     db.load_fonts_dir(fonts_dir);
-    FontSystem::new_with_locale_and_db("en-US".to_string(), db)
+    db
+}
+
+fn build_font_system(fonts_dir: &Path) -> FontSystem {
+    let platform = PlatformFallback;
+
+    let common = configured_font_families()
+        .fallback
+        .iter()
+        .chain(platform.common_fallback())
+        .copied()
+        .collect();
+
+    FontSystem::new_with_locale_and_db_and_fallback(
+        "en-US".to_string(),
+        load_fonts_db(fonts_dir),
+        ConfiguredFallback { common, platform },
+    )
 }
 
 thread_local! {
@@ -3,13 +3,13 @@ use crate::render::{
     colors::{self, Color, ContextExt},
     draw::{
         font_options::FontAndLayoutOptions,
-        font_system::{scale_outline, stamp_outline, with_font_system, with_scale_context},
+        font_system::{
+            font_family, scale_outline, stamp_outline, with_font_system, with_scale_context,
+        },
     },
 };
 use cairo::Context;
-use cosmic_text::{
-    Attrs, AttrsList, Buffer, BufferLine, Family, LineEnding, Metrics, Shaping, Wrap,
-};
+use cosmic_text::{Attrs, AttrsList, Buffer, BufferLine, LineEnding, Metrics, Shaping, Wrap};
 use geo::{Point, Rect};
 use std::borrow::Cow;
 
@@ -94,7 +94,7 @@ pub fn draw_text(
         Cow::Borrowed(text)
     };
 
-    let family = Family::Name(if narrow { "PT Sans Narrow" } else { "PT Sans" });
+    let family = font_family(narrow);
 
     let base_attrs = Attrs::new()
         .family(family)
@@ -3,13 +3,15 @@ use crate::render::{
     colors::{self, Color, ContextExt},
     draw::{
         font_options::FontAndLayoutOptions,
-        font_system::{scale_outline, stamp_outline, with_font_system, with_scale_context},
+        font_system::{
+            font_family, scale_outline, stamp_outline, with_font_system, with_scale_context,
+        },
         offset_line::offset_line_string,
     },
 };
 use cairo::Context;
 use cosmic_text::{
-    Attrs, Buffer, Family, Metrics, Shaping, Wrap,
+    Attrs, Buffer, Metrics, Shaping, Wrap,
     fontdb::{ID as FontId, Weight as FdbWeight},
 };
 use geo::Vector2DOps;
@@ -437,11 +439,7 @@ struct ClusterInfo {
 /// Cluster positions, ink extents, and logical extents are all relative to
 /// the cluster's pen origin (at the baseline).
 fn collect_clusters(text: &str, flo: &FontAndLayoutOptions) -> Vec<ClusterInfo> {
-    let family = Family::Name(if flo.narrow {
-        "PT Sans Narrow"
-    } else {
-        "PT Sans"
-    });
+    let family = font_family(flo.narrow);
     let attrs = Attrs::new()
         .family(family)
         .weight(flo.weight)
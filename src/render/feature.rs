@@ -10,7 +10,6 @@ use std::collections::HashMap;
 pub enum LegendValue {
     String(&'static str),
     Bool(bool),
-    #[allow(dead_code)]
     F32(f32),
     F64(f64),
     I16(i16),
@@ -29,6 +28,12 @@ impl From<f64> for LegendValue {
     }
 }
 
+impl From<f32> for LegendValue {
+    fn from(value: f32) -> Self {
+        Self::F32(value)
+    }
+}
+
 impl From<i16> for LegendValue {
     fn from(value: i16) -> Self {
         Self::I16(value)
@@ -1,6 +1,7 @@
 use crate::render::{FeatureError, GeomError, svg_repo::SvgRepoError};
-use std::fmt;
+use std::{error::Error as _, fmt, io};
 use thiserror::Error;
+use tokio_postgres::error::SqlState;
 
 #[derive(Error, Debug)]
 pub enum LayerRenderError {
@@ -29,8 +30,37 @@ pub enum LayerRenderError {
     FeatureError(#[from] FeatureError),
 }
 
+impl LayerRenderError {
+    /// Whether the DB connection broke during the query, so that the query may succeed on
+    /// another connection.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, Self::Postgres(PostgresRenderError(err)) if is_connection_error(err))
+    }
+}
+
 pub type LayerRenderResult = Result<(), LayerRenderError>;
 
+/// Tells errors of the connection (closed socket, I/O failure, server shutdown) from errors of the
+/// query itself.
+pub fn is_connection_error(err: &tokio_postgres::Error) -> bool {
+    if err.is_closed() {
+        return true;
+    }
+
+    if let Some(code) = err.code() {
+        // class 08 is "connection exception"
+        return code.code().starts_with("08")
+            || [
+                SqlState::ADMIN_SHUTDOWN,
+                SqlState::CRASH_SHUTDOWN,
+                SqlState::CANNOT_CONNECT_NOW,
+            ]
+            .contains(code);
+    }
+
+    err.source().is_some_and(|source| source.is::<io::Error>())
+}
+
 #[derive(Debug)]
 pub struct PostgresRenderError(pub tokio_postgres::Error);
 
@@ -87,3 +117,74 @@ impl From<GeomError> for LayerRenderError {
         Self::FeatureError(err.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+    use tokio_postgres::NoTls;
+
+    fn message(tag: u8, body: &[u8]) -> Vec<u8> {
+        let mut message = vec![tag];
+        message.extend_from_slice(&(body.len() as i32 + 4).to_be_bytes());
+        message.extend_from_slice(body);
+        message
+    }
+
+    /// Accepts the startup, replies to the first query with `reply` and hangs up.
+    async fn fake_server(mut stream: DuplexStream, reply: Vec<u8>) {
+        let len = stream.read_i32().await.expect("startup length");
+        let mut startup = vec![0; len as usize - 4];
+        stream.read_exact(&mut startup).await.expect("startup");
+
+        let mut ready = message(b'R', &0i32.to_be_bytes());
+        ready.extend(message(b'Z', b"I"));
+        stream.write_all(&ready).await.expect("ready");
+
+        let _tag = stream.read_u8().await.expect("query tag");
+        let len = stream.read_i32().await.expect("query length");
+        let mut query = vec![0; len as usize - 4];
+        stream.read_exact(&mut query).await.expect("query");
+
+        stream.write_all(&reply).await.expect("reply");
+    }
+
+    async fn query_error(reply: Vec<u8>) -> tokio_postgres::Error {
+        let (client_stream, server_stream) = tokio::io::duplex(4096);
+
+        let server = tokio::spawn(fake_server(server_stream, reply));
+
+        let (client, connection) = tokio_postgres::Config::new()
+            .user("test")
+            .connect_raw(client_stream, NoTls)
+            .await
+            .expect("connected");
+
+        tokio::spawn(connection);
+
+        let result = client.simple_query("SELECT 1").await;
+
+        server.await.expect("server finished");
+
+        result.expect_err("query failed")
+    }
+
+    #[tokio::test]
+    async fn dropped_connection_is_connection_error() {
+        let err = query_error(Vec::new()).await;
+
+        assert!(is_connection_error(&err), "{err:?}");
+        assert!(LayerRenderError::from(err).is_connection_error());
+    }
+
+    #[tokio::test]
+    async fn query_error_is_not_connection_error() {
+        let mut reply = message(b'E', b"SERROR\0C42601\0Msyntax error\0\0");
+        reply.extend(message(b'Z', b"I"));
+
+        let err = query_error(reply).await;
+
+        assert!(err.as_db_error().is_some(), "{err:?}");
+        assert!(!is_connection_error(&err));
+    }
+}
@@ -7,7 +7,7 @@ use crate::render::{
     layer_render_error::LayerRenderResult,
     projectable::TileProjectable,
 };
-use cairo::{Context, Format, ImageSurface, Operator};
+use cairo::{Context, FillRule, Format, ImageSurface, Operator};
 use geo::Geometry;
 use image::{GrayImage, imageops};
 
@@ -29,6 +29,8 @@ pub fn render(ctx: &Ctx, context: &Context, coverage_polygon_merc: &Geometry) ->
                 let coverage_ctx = cairo::Context::new(&coverage_surface)?;
 
                 coverage_ctx.translate(pad, pad);
+                // Even-odd keeps holes (excluded enclaves) unfilled regardless of ring orientation.
+                coverage_ctx.set_fill_rule(FillRule::EvenOdd);
                 path_geometry(&coverage_ctx, &coverage_geometry);
                 coverage_ctx.set_source_rgba(1.0, 1.0, 1.0, 1.0);
                 coverage_ctx.fill()?;
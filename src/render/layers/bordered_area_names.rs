@@ -1,5 +1,5 @@
 use crate::render::{
-    Feature,
+    Feature, FeatureError,
     collision::Collision,
     colors,
     ctx::Ctx,
@@ -9,13 +9,14 @@ use crate::render::{
         text::{TextOptions, draw_text},
         text_on_line::{Align, Distribution, Repeat, TextOnLineOptions, draw_text_on_line},
     },
-    layer_render_error::LayerRenderResult,
+    layer_render_error::{LayerRenderError, LayerRenderResult},
     layers::national_park_names::REPLACEMENTS,
     projectable::TileProjectable,
     regex_replacer::replace,
 };
 use cairo::Context;
 use cosmic_text::Style;
+use std::collections::HashSet;
 
 pub async fn query_centroids(
     ctx: &Ctx,
@@ -23,6 +24,7 @@ pub async fn query_centroids(
 ) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
     let sql = "
         SELECT
+            type,
             name,
             ST_Centroid(geometry) AS geometry
         FROM
@@ -59,11 +61,57 @@ pub async fn query_borders(
     client.query(sql, &ctx.bbox_query_params(Some(1024.0)).as_params()).await
 }
 
+/// Label priority of the bordered area types; national parks win over reserves.
+fn type_priority(typ: &str) -> u8 {
+    match typ {
+        "national_park" => 0,
+        "protected_area" => 1,
+        "nature_reserve" => 2,
+        _ => 3,
+    }
+}
+
+/// Tries to place the `(type, name)` candidates by their index in the order of type priority
+/// (stable, so the query order is kept within a type), skipping names that are already labeled.
+/// A name is added to `labeled` only once `place` reports it was placed, so a lower priority
+/// candidate of the same name gets its chance if the label of the higher one collides.
+fn place_labels<E>(
+    candidates: &[(&str, &str)],
+    labeled: &mut HashSet<String>,
+    mut place: impl FnMut(usize) -> Result<bool, E>,
+) -> Result<(), E> {
+    let mut indices: Vec<_> = (0..candidates.len()).collect();
+
+    indices.sort_by_key(|&i| type_priority(candidates[i].0));
+
+    for i in indices {
+        let name = candidates[i].1;
+
+        if name.is_empty() || labeled.contains(name) {
+            continue;
+        }
+
+        if place(i)? {
+            labeled.insert(name.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+fn candidates(rows: &[Feature]) -> Result<Vec<(&str, &str)>, FeatureError> {
+    rows.iter()
+        .map(|row| Ok((row.get_string("type")?, row.get_string("name")?)))
+        .collect()
+}
+
+/// Renders the centroid labels, skipping names already labeled along the borders.
 pub fn render_centroids(
     ctx: &Ctx,
     context: &Context,
     centroids: Vec<Feature>,
     collision: &mut Collision,
+    labeled: &mut HashSet<String>,
 ) -> LayerRenderResult {
     let _span = tracy_client::span!("protected_area_names::render_centroids");
 
@@ -77,24 +125,28 @@ pub fn render_centroids(
         ..TextOptions::default()
     };
 
-    for row in centroids {
-        draw_text(
+    place_labels(&candidates(&centroids)?, labeled, |i| {
+        let row = &centroids[i];
+
+        let placement = draw_text(
             context,
-            Some(collision),
+            Some(&mut *collision),
             &row.get_point()?.project_to_tile(&ctx.tile_projector),
             row.get_string("name")?,
             &text_options,
         )?;
-    }
 
-    Ok(())
+        Ok::<_, LayerRenderError>(placement.is_some())
+    })
 }
 
+/// Renders the labels along the borders, adding the names to `labeled`.
 pub fn render_borders(
     ctx: &Ctx,
     context: &Context,
     borders: Vec<Feature>,
     collision: &mut Collision,
+    labeled: &mut HashSet<String>,
 ) -> LayerRenderResult {
     let _span = tracy_client::span!("protected_area_names::render_borders");
 
@@ -114,29 +166,101 @@ pub fn render_borders(
         ..TextOnLineOptions::default()
     };
 
-    for row in borders {
+    place_labels(&candidates(&borders)?, labeled, |i| {
+        let row = &borders[i];
+
         text_options.color = match row.get_string("type")? {
             "national_park" | "protected_area" => colors::PROTECTED,
             "winter_sports" => colors::WATER,
-            _ => continue,
+            _ => return Ok(false),
         };
 
-        let name = row.get_string("name")?;
+        let name = replace(row.get_string("name")?, &REPLACEMENTS);
 
         let geom = row.get_geometry()?.project_to_tile(&ctx.tile_projector);
 
+        let mut placed = false;
+
         walk_geometry_line_strings(&geom, &mut |geom| {
-            let _drawn = draw_text_on_line(
-                context,
-                geom,
-                &replace(name, &REPLACEMENTS),
-                Some(collision),
-                &text_options,
-            )?;
+            placed |=
+                draw_text_on_line(context, geom, &name, Some(&mut *collision), &text_options)?;
 
             cairo::Result::Ok(())
         })?;
+
+        Ok::<_, LayerRenderError>(placed)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Indices of the candidates placed, all placements succeeding but the `colliding` ones.
+    fn placed(
+        candidates: &[(&str, &str)],
+        labeled: &mut HashSet<String>,
+        colliding: &[usize],
+    ) -> Vec<usize> {
+        let mut placed = Vec::new();
+
+        place_labels::<()>(candidates, labeled, |i| {
+            let ok = !colliding.contains(&i);
+
+            if ok {
+                placed.push(i);
+            }
+
+            Ok(ok)
+        })
+        .expect("placement should not fail");
+
+        placed
     }
 
-    Ok(())
+    #[test]
+    fn national_park_wins_over_overlapping_reserve_of_same_name() {
+        let mut labeled = HashSet::new();
+
+        let borders = [("national_park", "Slovenský raj")];
+
+        assert_eq!(placed(&borders, &mut labeled, &[]), vec![0]);
+
+        let centroids = [
+            ("nature_reserve", "Slovenský raj"),
+            ("protected_area", "Slovenský raj"),
+            ("nature_reserve", "Prielom Hornádu"),
+        ];
+
+        assert_eq!(placed(&centroids, &mut labeled, &[]), vec![2]);
+    }
+
+    #[test]
+    fn protected_area_wins_over_reserve_of_same_name() {
+        let mut labeled = HashSet::new();
+
+        let centroids = [
+            ("nature_reserve", "Tichá dolina"),
+            ("nature_reserve", "Kôprová dolina"),
+            ("protected_area", "Tichá dolina"),
+        ];
+
+        assert_eq!(placed(&centroids, &mut labeled, &[]), vec![2, 1]);
+    }
+
+    #[test]
+    fn colliding_label_leaves_name_to_next_candidate() {
+        let mut labeled = HashSet::new();
+
+        let borders = [("national_park", "Slovenský raj")];
+
+        assert_eq!(placed(&borders, &mut labeled, &[0]), Vec::<usize>::new());
+
+        let centroids = [
+            ("nature_reserve", "Slovenský raj"),
+            ("protected_area", "Slovenský raj"),
+        ];
+
+        assert_eq!(placed(&centroids, &mut labeled, &[1]), vec![0]);
+    }
 }
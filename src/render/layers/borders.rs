@@ -11,7 +11,8 @@ use cairo::Context;
 pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
     let sql = "
         SELECT
-            geometry
+            geometry,
+            maritime
         FROM
             osm_country_members
         WHERE
@@ -21,22 +22,43 @@ pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tok
     client.query(sql, &ctx.bbox_query_params(Some(10.0)).as_params()).await
 }
 
-pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderResult {
+/// Maritime border segments are drawn thinner and dashed with `maritime`, otherwise omitted.
+pub fn render(
+    ctx: &Ctx,
+    context: &Context,
+    rows: Vec<Feature>,
+    maritime: bool,
+) -> LayerRenderResult {
     let _span = tracy_client::span!("borders::render");
 
     context.push_group();
 
+    let width = if ctx.zoom <= 10 {
+        6.0f64.mul_add(1.4f64.powf(ctx.zoom as f64 - 11.0), 0.5)
+    } else {
+        6.0
+    };
+
     for row in rows {
+        let is_maritime = row.get_bool("maritime")?;
+
+        if is_maritime && !maritime {
+            continue;
+        }
+
         let geometry = row.get_geometry()?.project_to_tile(&ctx.tile_projector);
 
-        context.set_dash(&[], 0.0);
-        context.set_source_color(colors::ADMIN_BORDER);
-        context.set_line_width(if ctx.zoom <= 10 {
-            6.0f64.mul_add(1.4f64.powf(ctx.zoom as f64 - 11.0), 0.5)
+        if is_maritime {
+            context.set_dash(&[width, width], 0.0);
+            context.set_line_width(width / 2.0);
+            context.set_line_cap(cairo::LineCap::Butt);
         } else {
-            6.0
-        });
-        context.set_line_cap(cairo::LineCap::Square);
+            context.set_dash(&[], 0.0);
+            context.set_line_width(width);
+            context.set_line_cap(cairo::LineCap::Square);
+        }
+
+        context.set_source_color(colors::ADMIN_BORDER);
         context.set_line_join(cairo::LineJoin::Round);
         path_geometry(context, &geometry);
         context.stroke()?;
@@ -43,9 +43,11 @@ pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>, mask: bool) -> L
             context.set_source_color(colors::INDUSTRIAL);
             context.fill_preserve()?;
 
-            context.set_line_width(1.0);
+            // the casing sets the deck apart from the landcover below; roads on it render later
+            context.set_line_width(if ctx.zoom >= 17 { 2.0 } else { 1.5 });
+            context.set_line_join(cairo::LineJoin::Round);
             context.set_dash(&[], 0.0);
-            context.set_source_color(colors::BUILDING);
+            context.set_source_color(colors::BRIDGE_AREA_CASING);
             context.stroke()?;
         }
     }
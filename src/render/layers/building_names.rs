@@ -22,6 +22,7 @@ pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tok
             osm_buildings.name <> '' AND
             osm_buildings.geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5) AND
             osm_buildings.type <> 'no' AND
+            osm_buildings.type NOT LIKE 'part:%' AND
             osm_landcovers.osm_id IS NULL AND
             osm_pois.osm_id IS NULL AND
             osm_place_of_worships.osm_id IS NULL AND
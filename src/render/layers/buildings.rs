@@ -1,28 +1,57 @@
 use crate::render::{
     Feature,
-    colors::{self, ContextExt},
+    colors::{self, Color, ContextExt},
     ctx::Ctx,
     draw::path_geom::path_geometry,
     layer_render_error::LayerRenderResult,
     projectable::TileProjectable,
 };
-use cairo::Context;
-
-pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
-    let sql = "
+use cairo::{Context, LinearGradient};
+use colorsys::Rgb;
+use geo::{BoundingRect, Geometry};
+
+/// Building parts refine the building outlines they lie within, so they are only drawn along with
+/// the roof shading.
+pub async fn query(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+    roof_shading: bool,
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    let sql = format!(
+        "
         SELECT
             type,
+            COALESCE(tags->'building:colour', tags->'colour', '') AS colour,
+            COALESCE(tags->'roof:shape', '') AS roof_shape,
+            COALESCE(tags->'roof:colour', '') AS roof_colour,
             geometry
         FROM
             osm_buildings
         WHERE
             geometry && ST_MakeEnvelope($1, $2, $3, $4, 3857)
-    ";
+            {}
+        ORDER BY
+            type LIKE 'part:%',
+            osm_id
+    ",
+        if roof_shading {
+            ""
+        } else {
+            "AND type NOT LIKE 'part:%'"
+        }
+    );
 
-    client.query(sql, &ctx.bbox_query_params(None).as_params()).await
+    client
+        .query(&sql, &ctx.bbox_query_params(None).as_params())
+        .await
 }
 
-pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderResult {
+pub fn render(
+    ctx: &Ctx,
+    context: &Context,
+    rows: Vec<Feature>,
+    roof_shading: bool,
+) -> LayerRenderResult {
     let _span = tracy_client::span!("buildings::render");
 
     context.save()?;
@@ -89,8 +118,29 @@ pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderRe
 
             context.pop_group_to_source()?;
             context.paint()?;
+        } else if roof_shading {
+            let roof_colour = row.get_string("roof_colour")?;
+
+            context.set_source_color(fill_color(if roof_colour.is_empty() {
+                row.get_string("colour")?
+            } else {
+                roof_colour
+            }));
+            context.fill_preserve()?;
+
+            if is_pitched(row.get_string("roof_shape")?) {
+                shade_roof(context, &geom)?;
+            }
+
+            context.set_source_color_a(colors::BLACK, 0.2);
+            context.set_line_width(0.5);
+            context.stroke()?;
         } else {
-            context.set_source_color(colors::BUILDING);
+            context.set_source_color(if ctx.zoom >= 18 {
+                fill_color(row.get_string("colour")?)
+            } else {
+                colors::BUILDING
+            });
             context.fill()?;
         }
     }
@@ -99,3 +149,47 @@ pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderRe
 
     Ok(())
 }
+
+fn fill_color(colour: &str) -> Color {
+    Rgb::from_hex_str(colour).map_or(colors::BUILDING, |rgb| {
+        let rgb = rgb.as_ratio();
+
+        (rgb.r(), rgb.g(), rgb.b())
+    })
+}
+
+/// Flat roofs (and untagged ones, mostly flat in town centers) get no shading.
+fn is_pitched(roof_shape: &str) -> bool {
+    !matches!(roof_shape, "" | "flat")
+}
+
+/// Lights the roof from the north-west, as the hillshading, by a gradient across its extent.
+fn shade_roof(context: &Context, geom: &Geometry) -> cairo::Result<()> {
+    let Some(rect) = geom.bounding_rect() else {
+        return Ok(());
+    };
+
+    let (min, max) = (rect.min(), rect.max());
+
+    let gradient = LinearGradient::new(min.x, min.y, max.x, max.y);
+    gradient.add_color_stop_rgba(0.0, 1.0, 1.0, 1.0, 0.35);
+    gradient.add_color_stop_rgba(0.5, 1.0, 1.0, 1.0, 0.0);
+    gradient.add_color_stop_rgba(0.5, 0.0, 0.0, 0.0, 0.0);
+    gradient.add_color_stop_rgba(1.0, 0.0, 0.0, 0.0, 0.25);
+
+    context.set_source(&gradient)?;
+    context.fill_preserve()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_pitched_roofs_are_shaded() {
+        assert!(is_pitched("gabled"));
+        assert!(is_pitched("hipped"));
+        assert!(!is_pitched("flat"));
+        assert!(!is_pitched(""));
+    }
+}
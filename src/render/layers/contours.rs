@@ -1,5 +1,5 @@
 use crate::render::{
-    Feature,
+    ContourUnit, Feature,
     colors::{self, ContextExt},
     ctx::Ctx,
     draw::{
@@ -18,6 +18,8 @@ pub async fn query(
     ctx: &Ctx,
     client: &tokio_postgres::Client,
     country: Option<&str>,
+    interval: u32,
+    unit: ContourUnit,
 ) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
     let simplify_factor: f64 = match ctx.zoom {
         ..=12 => 2000.0,
@@ -27,21 +29,6 @@ pub async fn query(
         _ => 0.0,
     };
 
-    let (height_filter, width_case) = match ctx.zoom {
-        12 => (
-            "height_m % 50 = 0",
-            "CASE WHEN height_m % 50 = 0 THEN 0.2 ELSE 0.0 END",
-        ),
-        13 | 14 => (
-            "height_m % 20 = 0",
-            "CASE WHEN height_m % 100 = 0 THEN 0.4 WHEN height_m % 20 = 0 THEN 0.2 ELSE 0.0 END",
-        ),
-        _ => (
-            "height_m % 10 = 0",
-            "CASE WHEN height_m % 100 = 0 THEN 0.6 WHEN height_m % 10 = 0 THEN 0.3 ELSE 0.0 END",
-        ),
-    };
-
     let params = ctx.bbox_query_params(Some(8.0));
 
     let (geometry_expr, params) = if simplify_factor > 0.0 {
@@ -55,17 +42,37 @@ pub async fn query(
 
     let table = format!("contours_{}", country.unwrap_or("fallback"));
 
+    let index_interval = interval * 5;
+
+    let (height_expr, is_index_expr, interval_cond) = match unit {
+        ContourUnit::Meters => (
+            "height_m".to_string(),
+            format!("height_m % {index_interval} = 0"),
+            format!("height_m % {interval} = 0"),
+        ),
+        ContourUnit::Feet => {
+            // of the 1 m sources, the contour nearest to each feet height stands for it
+            let steps = format!("round(height_m / 0.3048 / {interval})");
+
+            (
+                format!("({steps} * {interval})::smallint"),
+                format!("{steps}::integer % 5 = 0"),
+                format!("height_m = round({steps} * {interval} * 0.3048)"),
+            )
+        }
+    };
+
     let sql = format!(
         "
         SELECT
             {geometry_expr} AS geometry,
-            height_m,
-            ({width_case})::double precision AS width
+            {height_expr} AS height,
+            {is_index_expr} AS is_index
         FROM
             {table}
         WHERE
             wkb_geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
-            AND {height_filter}
+            AND {interval_cond}
         "
     );
 
@@ -84,16 +91,20 @@ pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderRe
     context.save()?;
 
     for row in rows {
-        let height = row.get_i16("height_m")?;
+        let height = row.get_i16("height")?;
 
-        let width = row.get_f64("width")?;
+        let is_index = row.get_bool("is_index")?;
 
-        let labels = match zoom {
-            13..=14 => height % 100 == 0,
-            15.. => height % 50 == 0,
-            _ => false,
+        let (intermediate_width, index_width) = contour_widths(zoom);
+
+        let width = if is_index {
+            index_width
+        } else {
+            intermediate_width
         };
 
+        let labels = zoom >= 13 && is_index;
+
         let geom = row.get_line_string()?.project_to_tile(&ctx.tile_projector);
 
         context.set_dash(&[], 0.0);
@@ -130,3 +141,39 @@ pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderRe
 
     Ok(())
 }
+
+/// Line widths of intermediate and index contours.
+fn contour_widths(zoom: u8) -> (f64, f64) {
+    match zoom {
+        ..=12 => (0.2, 0.4),
+        13 => (0.2, 0.5),
+        14 => (0.25, 0.6),
+        15 => (0.3, 0.7),
+        _ => (0.35, 0.8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_contours_are_heavier() {
+        for zoom in 12..=18 {
+            let (intermediate, index) = contour_widths(zoom);
+
+            assert!(index > intermediate, "zoom {zoom}");
+        }
+    }
+
+    #[test]
+    fn widths_step_up_from_z13_to_z16() {
+        for zoom in 13..16 {
+            let (intermediate, index) = contour_widths(zoom);
+            let (next_intermediate, next_index) = contour_widths(zoom + 1);
+
+            assert!(next_intermediate > intermediate, "zoom {zoom}");
+            assert!(next_index > index, "zoom {zoom}");
+        }
+    }
+}
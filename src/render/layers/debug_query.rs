@@ -0,0 +1,81 @@
+use super::{
+    aerialway_names, bordered_area_names, borders, bridge_areas, building_names, buildings,
+    country_names, embankments, feature_lines, fixmes, geonames, golf, highway_names, housenumbers,
+    landcover, landcover_names, locality_names, military_areas, national_park_names,
+    pitch_markings, place_names, pois, power_towers_poles, protected_areas, reefs,
+    road_access_restrictions, roads, sea, seamarks, solar_power_plants, special_park_names,
+    special_parks, trees, valleys_ridges, water_area_names, water_areas, water_line_names,
+    water_lines, winter_sports_names,
+};
+use crate::render::{ctx::Ctx, projectable::TileProjector, size::Size};
+use geo::Rect;
+
+/// Runs the selection query of the layer as the renderer does for a 256 px tile of the bbox, to
+/// inspect the selected features. Returns `None` for unknown layers and for layers whose query
+/// depends on more than the tile (contours, routes, highlight).
+pub async fn query_layer(
+    layer: &str,
+    bbox: Rect<f64>,
+    zoom: u8,
+    client: &tokio_postgres::Client,
+) -> Option<Result<Vec<tokio_postgres::Row>, tokio_postgres::Error>> {
+    let size = Size::new(256, 256);
+
+    let ctx = &Ctx {
+        bbox,
+        size,
+        zoom,
+        tile_projector: TileProjector::new(bbox, size),
+        scale: 1.0,
+        legend: None,
+    };
+
+    Some(match layer {
+        "aerialway_names" => aerialway_names::query(ctx, client).await,
+        "bordered_area_names_borders" => bordered_area_names::query_borders(ctx, client).await,
+        "bordered_area_names_centroids" => bordered_area_names::query_centroids(ctx, client).await,
+        "borders" | "country_borders" => borders::query(ctx, client).await,
+        "bridge_areas" => bridge_areas::query(ctx, client).await,
+        "building_names" => building_names::query(ctx, client).await,
+        "buildings" => buildings::query(ctx, client, false).await,
+        "country_names" => country_names::query(ctx, client).await,
+        "embankment_top_paths" => embankments::query_top_paths(ctx, client).await,
+        "embankments" => embankments::query(ctx, client).await,
+        "feature_lines" => feature_lines::query(ctx, client).await,
+        "fixmes_line" => fixmes::query_lines(ctx, client).await,
+        "fixmes_points" => fixmes::query_points(ctx, client).await,
+        "geonames" => geonames::query(ctx, client).await,
+        "golf" => golf::query(ctx, client).await,
+        "highway_names" => highway_names::query(ctx, client).await,
+        "housenumbers" => housenumbers::query(ctx, client).await,
+        "landcover_names" => landcover_names::query(ctx, client).await,
+        "landcovers" => landcover::query(ctx, client, &[]).await,
+        "locality_names" => locality_names::query(ctx, client).await,
+        "military_areas" => military_areas::query(ctx, client).await,
+        "national_park_names" => national_park_names::query(ctx, client).await,
+        "national_park_names_borders" => national_park_names::query_borders(ctx, client).await,
+        "pitch_markings" => pitch_markings::query(ctx, client).await,
+        "place_names" => place_names::query(ctx, client).await,
+        "pois" => pois::query(ctx, client, false, None).await,
+        "power_towers_poles" => power_towers_poles::query(ctx, client).await,
+        "protected_areas_areas" => protected_areas::query_areas(ctx, client).await,
+        "protected_areas_borders" => protected_areas::query_borders(ctx, client).await,
+        "reefs" => reefs::query(ctx, client).await,
+        "road_access_restrictions" => road_access_restrictions::query(ctx, client).await,
+        "roads" => roads::query(ctx, client).await,
+        "sea" => sea::query(ctx, client).await,
+        "seamarks" => seamarks::query(ctx, client).await,
+        "solar_power_plants" => solar_power_plants::query(ctx, client).await,
+        "special_park_names" => special_park_names::query(ctx, client).await,
+        "special_parks" => special_parks::query(ctx, client).await,
+        "trees" => trees::query(ctx, client).await,
+        "valleys" => valleys_ridges::query_valleys(ctx, client).await,
+        "ridges" => valleys_ridges::query_ridges(ctx, client).await,
+        "water_area_names" => water_area_names::query(ctx, client).await,
+        "water_areas" => water_areas::query(ctx, client).await,
+        "water_line_names" => water_line_names::query(ctx, client).await,
+        "water_lines" => water_lines::query(ctx, client).await,
+        "winter_sports_names" => winter_sports_names::query(ctx, client).await,
+        _ => return None,
+    })
+}
@@ -1,6 +1,11 @@
 use crate::render::{
-    Feature, ctx::Ctx, draw::line_pattern::draw_line_pattern,
-    layer_render_error::LayerRenderResult, projectable::TileProjectable, svg_repo::SvgRepo,
+    Feature,
+    colors::{self, ContextExt},
+    ctx::Ctx,
+    draw::{line_pattern::draw_line_pattern, path_geom::path_geometry},
+    layer_render_error::LayerRenderResult,
+    projectable::TileProjectable,
+    svg_repo::SvgRepo,
 };
 use cairo::Context;
 
@@ -34,3 +39,81 @@ pub fn render(
 
     Ok(())
 }
+
+/// Crests of dykes and embankments carrying a walkable way, e.g. levee-top paths. The way is
+/// either the dyke itself or a separate way running along its crest; for the latter only the
+/// part of the crest along the way is selected, and ways merely crossing the dyke are skipped.
+pub async fn query_top_paths(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    // a separate way is on the crest within 4 map units, along at least 20 of them
+    let sql = "
+        SELECT
+            osm_feature_lines.geometry
+        FROM
+            osm_feature_lines
+        JOIN
+            osm_roads USING (osm_id)
+        WHERE
+            osm_feature_lines.type IN ('dyke', 'embankment') AND
+            (
+                osm_roads.type IN ('path', 'footway', 'bridleway', 'cycleway', 'track', 'steps') OR
+                osm_roads.foot IN ('yes', 'designated', 'permissive')
+            ) AND
+            osm_feature_lines.geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
+        UNION ALL
+        SELECT
+            crest.geometry
+        FROM
+            osm_feature_lines
+        JOIN
+            osm_roads ON
+                osm_roads.osm_id <> osm_feature_lines.osm_id AND
+                osm_roads.geometry && ST_Expand(osm_feature_lines.geometry, 4)
+        CROSS JOIN LATERAL (
+            SELECT
+                ST_CollectionExtract(
+                    ST_Intersection(osm_feature_lines.geometry, ST_Buffer(osm_roads.geometry, 4)),
+                    2
+                ) AS geometry
+        ) AS crest
+        WHERE
+            osm_feature_lines.type IN ('dyke', 'embankment') AND
+            (
+                osm_roads.type IN ('path', 'footway', 'bridleway', 'cycleway', 'track', 'steps') OR
+                osm_roads.foot IN ('yes', 'designated', 'permissive')
+            ) AND
+            osm_feature_lines.geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5) AND
+            ST_Length(crest.geometry) >= 20
+    ";
+
+    client
+        .query(sql, &ctx.bbox_query_params(Some(8.0)).as_params())
+        .await
+}
+
+/// Fades the crest of the dyke or embankment hatching so that the path drawn on top of it
+/// stays readable.
+pub fn render_top_paths(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderResult {
+    let _span = tracy_client::span!("embankments::render_top_paths");
+
+    context.save()?;
+
+    context.set_source_color_a(colors::WHITE, 0.8);
+    context.set_line_width(4.0);
+    context.set_line_cap(cairo::LineCap::Round);
+    context.set_line_join(cairo::LineJoin::Round);
+
+    for row in rows {
+        let geom = row.get_geometry()?.project_to_tile(&ctx.tile_projector);
+
+        path_geometry(context, &geom);
+
+        context.stroke()?;
+    }
+
+    context.restore()?;
+
+    Ok(())
+}
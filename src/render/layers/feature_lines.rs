@@ -4,7 +4,7 @@ use crate::render::{
     ctx::Ctx,
     draw::{
         line_pattern::{draw_line_pattern, draw_line_pattern_scaled},
-        path_geom::path_line_string,
+        path_geom::{path_line_string, path_line_string_with_offset},
     },
     layer_render_error::{LayerRenderError, LayerRenderResult},
     layers::{HillshadingDatasets, hillshading},
@@ -46,17 +46,30 @@ pub async fn query(
     }
 
     if ctx.zoom >= 14 {
-        types.push("minor_line");
+        types.extend([
+            "minor_line",
+            "alpine_coaster",
+            "roller_coaster",
+            "summer_toboggan",
+        ]);
     }
 
     if ctx.zoom >= 15 {
-        types.extend(["earth_bank", "dyke", "embankment", "gully", "cliff"]);
+        types.extend([
+            "earth_bank",
+            "dyke",
+            "embankment",
+            "gully",
+            "cliff",
+            "sinkhole",
+        ]);
     }
 
     if ctx.zoom >= 16 {
         types.extend([
             "city_wall",
             "hedge",
+            "lock_gate",
             "ditch",
             "fence",
             "retaining_wall",
@@ -64,14 +77,81 @@ pub async fn query(
         ]);
     }
 
+    // sinkhole areas are drawn as their clockwise outer rings so that the ticks point inwards
+    //
+    // flow_side of a weir is the side of the line (as the sign of the cross product) where the
+    // crossing waterway continues downstream, 0 if unknown
     let sql = "
         SELECT
-            geometry,
-            type,
-            tags
+            osm_feature_lines.osm_id,
+            osm_feature_lines.geometry,
+            osm_feature_lines.type,
+            osm_feature_lines.tags,
+            COALESCE(flow.side, 0)::smallint AS flow_side
         FROM
             osm_feature_lines
+        LEFT JOIN LATERAL (
+            SELECT
+                SIGN(
+                    (ST_X(b) - ST_X(a)) * (ST_Y(d) - ST_Y(a)) -
+                    (ST_Y(b) - ST_Y(a)) * (ST_X(d) - ST_X(a))
+                ) AS side
+            FROM
+                osm_waterways,
+                LATERAL (
+                    SELECT
+                        ST_GeometryN(
+                            ST_Intersection(osm_feature_lines.geometry, osm_waterways.geometry),
+                            1
+                        ) AS c
+                ) AS crossing,
+                LATERAL (
+                    SELECT
+                        ST_LineInterpolatePoint(
+                            osm_feature_lines.geometry,
+                            GREATEST(ST_LineLocatePoint(osm_feature_lines.geometry, c) - 0.01, 0)
+                        ) AS a,
+                        ST_LineInterpolatePoint(
+                            osm_feature_lines.geometry,
+                            LEAST(ST_LineLocatePoint(osm_feature_lines.geometry, c) + 0.01, 1)
+                        ) AS b,
+                        ST_LineInterpolatePoint(
+                            osm_waterways.geometry,
+                            LEAST(
+                                ST_LineLocatePoint(osm_waterways.geometry, c) +
+                                    5 / NULLIF(ST_Length(osm_waterways.geometry), 0),
+                                1
+                            )
+                        ) AS d
+                ) AS points
+            WHERE
+                osm_feature_lines.type = 'weir'
+                AND
+                osm_waterways.geometry && osm_feature_lines.geometry
+                AND
+                ST_Intersects(osm_waterways.geometry, osm_feature_lines.geometry)
+                AND
+                GeometryType(c) = 'POINT'
+            ORDER BY
+                ST_Length(osm_waterways.geometry) DESC
+            LIMIT 1
+        ) AS flow ON TRUE
+        WHERE
+            osm_feature_lines.type = ANY($6)
+            AND
+            osm_feature_lines.geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
+        UNION ALL
+        SELECT
+            osm_id,
+            ST_ExteriorRing((ST_Dump(ST_ForcePolygonCW(geometry))).geom) AS geometry,
+            type,
+            tags,
+            0::smallint AS flow_side
+        FROM
+            osm_landcovers
         WHERE
+            type = 'sinkhole'
+            AND
             type = ANY($6)
             AND
             geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
@@ -159,6 +239,22 @@ pub fn render(
                         context.set_source_color(colors::DAM_LINE);
                         context.set_line_width(3.0);
                         context.stroke()?;
+
+                        // teeth on the downstream side; tile y axis is flipped against the
+                        // projected one, hence the negation
+                        let flow_side = row.get_i16("flow_side")?;
+
+                        if flow_side != 0 {
+                            path_line_string_with_offset(
+                                context,
+                                &geom,
+                                -f64::from(flow_side) * 2.5,
+                            );
+
+                            context.set_dash(&[1.5, 3.0], 0.0);
+                            context.set_line_width(2.0);
+                            context.stroke()?;
+                        }
                     }
                 }
                 (2, 13.., "dam", false) => {
@@ -170,6 +266,14 @@ pub fn render(
                         context.stroke()?;
                     }
                 }
+                (2, 16.., "lock_gate", false) => {
+                    path_line_string(context, &geom);
+
+                    context.set_source_color(colors::DAM_LINE);
+                    context.set_line_width(4.0);
+                    context.set_line_cap(cairo::LineCap::Butt);
+                    context.stroke()?;
+                }
                 (2, 13.., "tree_row", false) => {
                     draw_line_pattern_scaled(
                         context,
@@ -198,7 +302,7 @@ pub fn render(
                 (2, 15.., "gully", true) => {
                     draw_line_pattern(context, ctx.size, &geom, 0.8, svg_repo.get("gully")?)?;
                 }
-                (2, 15.., "cliff", true) => {
+                (2, 15.., "cliff" | "sinkhole", true) => {
                     draw_line_pattern(context, ctx.size, &geom, 0.8, svg_repo.get("cliff")?)?;
 
                     context.set_source_color(colors::AREA_LABEL);
@@ -237,9 +341,11 @@ pub fn render(
                 (4, 16.., "hedge", false) => {
                     path_line_string(context, &geom);
 
+                    let width = hedge_width(ctx.zoom);
+
                     context.set_source_color(colors::PITCH);
-                    context.set_line_width(ctx.zoom as f64 - 14.0);
-                    context.set_dash(&[0.01, ctx.zoom as f64 - 14.0], 0.0);
+                    context.set_line_width(width);
+                    context.set_dash(&[0.01, width], 0.0);
                     context.set_line_join(cairo::LineJoin::Round);
                     context.set_line_cap(cairo::LineCap::Round);
                     context.stroke()?;
@@ -275,6 +381,25 @@ pub fn render(
 
                     context.paint()?;
                 }
+                (4, 14.., "alpine_coaster" | "roller_coaster" | "summer_toboggan", false) => {
+                    // a rail with sleepers, unlike the plain water slide of the roads
+                    context.push_group();
+
+                    path_line_string(context, &geom);
+
+                    context.set_source_color(colors::ATTRACTION_TRACK);
+                    context.set_dash(&[], 0.0);
+                    context.set_line_width(1.5);
+                    context.stroke_preserve()?;
+
+                    context.set_dash(&[1.0, 4.0], 0.0);
+                    context.set_line_width(if zoom >= 16 { 5.0 } else { 4.0 });
+                    context.stroke()?;
+
+                    context.pop_group_to_source()?;
+
+                    context.paint()?;
+                }
                 (4, 13.., "line", false) => {
                     path_line_string(context, &geom);
 
@@ -353,3 +478,28 @@ pub fn render(
 
     Ok(())
 }
+
+/// Width of the hedge line and spacing of its round "leaves", growing by a pixel per zoom.
+/// Clamped so that the dash never degenerates, whatever zoom it is called for.
+fn hedge_width(zoom: u8) -> f64 {
+    (f64::from(zoom) - 14.0).clamp(1.0, 6.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hedge_width_is_positive_and_grows_with_zoom() {
+        let widths: Vec<_> = (16..=20).map(hedge_width).collect();
+
+        assert!(widths.iter().all(|width| *width > 0.0));
+        assert!(widths.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn hedge_width_is_clamped() {
+        assert!((hedge_width(0) - 1.0).abs() < f64::EPSILON);
+        assert!((hedge_width(24) - 6.0).abs() < f64::EPSILON);
+    }
+}
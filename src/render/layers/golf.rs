@@ -0,0 +1,71 @@
+use crate::render::{
+    Feature,
+    colors::{self, ContextExt},
+    ctx::Ctx,
+    draw::path_geom::path_geometry,
+    layer_render_error::LayerRenderResult,
+    projectable::TileProjectable,
+};
+use cairo::Context;
+
+pub async fn query(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    let sql = "
+        SELECT
+            type,
+            geometry
+        FROM
+            osm_landcovers
+        WHERE
+            type IN ('golf_fairway', 'golf_tee', 'golf_green', 'golf_bunker') AND
+            geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
+        ORDER BY
+            CASE type
+                WHEN 'golf_fairway' THEN 0
+                WHEN 'golf_tee' THEN 1
+                WHEN 'golf_green' THEN 2
+                ELSE 3
+            END,
+            osm_id
+    ";
+
+    client
+        .query(sql, &ctx.bbox_query_params(Some(2.0)).as_params())
+        .await
+}
+
+pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderResult {
+    let _span = tracy_client::span!("golf::render");
+
+    context.save()?;
+
+    for row in rows {
+        let (fill, stroke) = match row.get_string("type")? {
+            "golf_fairway" => (colors::GOLF_FAIRWAY, None),
+            "golf_tee" | "golf_green" => (colors::GOLF_GREEN, Some(colors::GOLF_STROKE)),
+            "golf_bunker" => (colors::GOLF_BUNKER, Some(colors::GOLF_BUNKER_STROKE)),
+            _ => continue,
+        };
+
+        let geom = row.get_geometry()?.project_to_tile(&ctx.tile_projector);
+
+        path_geometry(context, &geom);
+
+        context.set_source_color(fill);
+
+        if let Some(stroke) = stroke {
+            context.fill_preserve()?;
+            context.set_source_color(stroke);
+            context.set_line_width(1.0);
+            context.stroke()?;
+        } else {
+            context.fill()?;
+        }
+    }
+
+    context.restore()?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::render::{
+    Feature,
+    colors::{self, ContextExt},
+    ctx::Ctx,
+    draw::path_geom::{path_geometry, walk_geometry_points},
+    layer_render_error::{LayerRenderError, LayerRenderResult},
+    projectable::TileProjectable,
+};
+use cairo::{Context, LineCap, LineJoin};
+use std::f64::consts::PI;
+
+pub async fn query(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+    osm_ids: &[i64],
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    let sql = "
+        SELECT DISTINCT ON (osm_id)
+            osm_id,
+            geometry
+        FROM (
+            SELECT osm_id, geometry FROM osm_pois
+            UNION ALL
+            SELECT osm_id, geometry FROM osm_sports
+            UNION ALL
+            SELECT osm_id, geometry FROM osm_roads
+            UNION ALL
+            SELECT osm_id, geometry FROM osm_feature_lines
+            UNION ALL
+            SELECT osm_id, geometry FROM osm_waterways
+            UNION ALL
+            SELECT osm_id, geometry FROM osm_waterareas
+            UNION ALL
+            SELECT osm_id, geometry FROM osm_landcovers
+            UNION ALL
+            SELECT osm_id, geometry FROM osm_buildings
+        ) AS features
+        WHERE
+            osm_id = ANY($6) AND
+            geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
+        ORDER BY
+            osm_id,
+            ST_Dimension(geometry) DESC
+    ";
+
+    client
+        .query(
+            sql,
+            &ctx.bbox_query_params(Some(8.0))
+                .push(osm_ids.to_vec())
+                .as_params(),
+        )
+        .await
+}
+
+pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderResult {
+    let _span = tracy_client::span!("highlight::render");
+
+    context.save()?;
+
+    context.set_line_join(LineJoin::Round);
+    context.set_line_cap(LineCap::Round);
+
+    for row in rows {
+        let geom = row.get_geometry()?.project_to_tile(&ctx.tile_projector);
+
+        path_geometry(context, &geom);
+
+        walk_geometry_points(&geom, &mut |point| {
+            context.new_sub_path();
+            context.arc(point.x(), point.y(), 8.0, 0.0, 2.0 * PI);
+
+            Ok::<(), LayerRenderError>(())
+        })?;
+
+        context.set_source_color_a(colors::WHITE, 0.8);
+        context.set_line_width(6.0);
+        context.stroke_preserve()?;
+
+        context.set_source_color(colors::HIGHLIGHT);
+        context.set_line_width(3.0);
+        context.stroke()?;
+    }
+
+    context.restore()?;
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
 use crate::render::{
+    HillshadingHierarchy,
     ctx::Ctx,
     layer_render_error::{LayerRenderError, LayerRenderResult},
     layers::hillshading_datasets::HillshadingDatasets,
 };
 use cairo::{Context, Format, ImageSurface};
 use gdal::Dataset;
+use std::collections::HashMap;
 
 pub enum Mode {
     Mask,
@@ -296,6 +298,66 @@ pub fn load_surface(
     Ok(surface)
 }
 
+/// Hillshading surfaces of the tile loaded by one pass and reused by the later ones, so that
+/// each raster is read once per tile.
+pub struct TileSurfaces {
+    /// Mask of every country of the hierarchy, in its order.
+    pub masks: Vec<(&'static str, Option<ImageSurface>)>,
+    /// Shadings loaded so far, by country.
+    pub shadings: HashMap<&'static str, Option<ImageSurface>>,
+}
+
+impl TileSurfaces {
+    pub fn load_masks(
+        ctx: &Ctx,
+        hierarchy: &HillshadingHierarchy,
+        shading_data: &mut HillshadingDatasets,
+    ) -> Result<Self, LayerRenderError> {
+        let masks = hierarchy
+            .entries()
+            .iter()
+            .map(|entry| {
+                Ok((
+                    entry.country,
+                    load_surface(ctx, entry.country, shading_data, Mode::Mask)?,
+                ))
+            })
+            .collect::<Result<_, LayerRenderError>>()?;
+
+        Ok(Self {
+            masks,
+            shadings: HashMap::new(),
+        })
+    }
+
+    pub fn mask(&self, country: &str) -> Option<&ImageSurface> {
+        self.masks
+            .iter()
+            .find(|(c, _)| *c == country)
+            .and_then(|(_, mask)| mask.as_ref())
+    }
+
+    /// Loads the shading of the country unless already loaded.
+    pub fn load_shading(
+        &mut self,
+        ctx: &Ctx,
+        country: &'static str,
+        shading_data: &mut HillshadingDatasets,
+    ) -> LayerRenderResult {
+        if !self.shadings.contains_key(country) {
+            let surface = load_surface(ctx, country, shading_data, Mode::Shading)?;
+
+            self.shadings.insert(country, surface);
+        }
+
+        Ok(())
+    }
+
+    pub fn shading(&self, country: &str) -> Option<&ImageSurface> {
+        self.shadings.get(country).and_then(Option::as_ref)
+    }
+}
+
 pub fn paint_surface(
     ctx: &Ctx,
     context: &Context,
@@ -1,6 +1,6 @@
 use gdal::Dataset;
 use std::{
-    collections::{HashMap, hash_map::Entry},
+    collections::{HashMap, HashSet, hash_map::Entry},
     path::{Path, PathBuf},
     time::{Duration, Instant},
 };
@@ -15,6 +15,8 @@ struct CachedDataset {
 pub struct HillshadingDatasets {
     base: PathBuf,
     datasets: HashMap<String, CachedDataset>,
+    /// Datasets opened at startup, never evicted.
+    preloaded: HashSet<String>,
 }
 
 impl HillshadingDatasets {
@@ -22,21 +24,35 @@ impl HillshadingDatasets {
         Self {
             base: base.as_ref().to_path_buf(),
             datasets: HashMap::new(),
+            preloaded: HashSet::new(),
+        }
+    }
+
+    /// Opens the datasets upfront and keeps them open, sparing the first tiles of each country
+    /// the opening latency.
+    pub fn preload<'a>(&mut self, names: impl IntoIterator<Item = &'a str>) {
+        for name in names {
+            if self.get(name).is_some() {
+                self.preloaded.insert(name.to_string());
+            }
         }
     }
 
     pub fn evict_unused(&mut self) {
         let now = Instant::now();
 
-        self.datasets
-            .retain(|_, cached| now.duration_since(cached.last_used_at) <= EVICT_AFTER);
+        let preloaded = &self.preloaded;
+
+        self.datasets.retain(|name, cached| {
+            preloaded.contains(name) || now.duration_since(cached.last_used_at) <= EVICT_AFTER
+        });
     }
 
     pub fn get(&mut self, name: &str) -> Option<&Dataset> {
         match self.datasets.entry(name.to_string()) {
             Entry::Occupied(occ) => Some(&occ.into_mut().dataset),
             Entry::Vacant(vac) => {
-                let full_path = self.base.join(name).join("final.tif");
+                let full_path = dataset_path(&self.base, name);
 
                 match Dataset::open(&full_path) {
                     Ok(dataset) => {
@@ -69,3 +85,31 @@ impl HillshadingDatasets {
 pub fn load_hillshading_datasets(base: impl AsRef<Path>) -> HillshadingDatasets {
     HillshadingDatasets::new(base)
 }
+
+/// Prints the file sizes of the datasets to preload and reports the missing ones.
+pub fn report_preloaded_datasets<'a>(base: &Path, names: impl IntoIterator<Item = &'a str>) {
+    let mut total = 0;
+
+    for name in names {
+        let path = dataset_path(base, name);
+
+        match std::fs::metadata(&path) {
+            Ok(metadata) => {
+                total += metadata.len();
+
+                println!(
+                    "Preloading hillshading {name}: {} ({} MiB)",
+                    path.display(),
+                    metadata.len() >> 20
+                );
+            }
+            Err(err) => eprintln!("Missing hillshading {name} {}: {err}", path.display()),
+        }
+    }
+
+    println!("Preloading hillshading datasets of {} MiB", total >> 20);
+}
+
+fn dataset_path(base: &Path, name: &str) -> PathBuf {
+    base.join(name).join("final.tif")
+}
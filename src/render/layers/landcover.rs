@@ -1,15 +1,20 @@
 use super::landcover_z_order::build_landcover_z_order_case;
 use crate::render::{
-    Feature,
-    colors::{self, Color, ContextExt, FOREST, GRASSY, SCRUB, HEATH, GLACIER, SCREE, FARMLAND, FARMYARD, BLACK, BEACH, ORCHARD, QUARRY, RESIDENTIAL, COMMERCIAL, INDUSTRIAL, BROWNFIELD, LANDFILL, DAM, HOSPITAL, ALLOTMENTS, PITCH, PITCH_STROKE, COLLEGE, NONE, PARKING, PARKING_STROKE, RECREATION_GROUND, SILO, SILO_STROKE, TREE},
+    Feature, HillshadingHierarchy,
+    colors::{self, Color, ContextExt, FOREST, GRASSY, SCRUB, HEATH, GLACIER, SCREE, FARMLAND, FARMYARD, BLACK, BEACH, ORCHARD, QUARRY, RESIDENTIAL, COMMERCIAL, INDUSTRIAL, BROWNFIELD, LANDFILL, DAM, HOSPITAL, ALLOTMENTS, PITCH, PITCH_STROKE, COLLEGE, NONE, PARKING, PARKING_STROKE, RECREATION_GROUND, SILO, SILO_STROKE, TREE, MUD, TIDALFLAT, MARINA, WATER_LABEL},
     ctx::Ctx,
     draw::path_geom::{path_geometry, path_line_string_with_offset, walk_geometry_line_strings},
     layer_render_error::LayerRenderResult,
+    layers::{
+        hillshading::{self, TileSurfaces},
+        hillshading_datasets::HillshadingDatasets,
+    },
     projectable::TileProjectable,
     svg_repo::SvgRepo,
     xyz::to_absolute_pixel_coords,
 };
-use cairo::{Context, Extend, Matrix, SurfacePattern};
+use cairo::{Context, Extend, Format, ImageSurface, Matrix, RecordingSurface, SurfacePattern};
+use geo::Geometry;
 use std::{collections::HashMap, sync::LazyLock};
 
 pub enum Paint {
@@ -33,6 +38,8 @@ pub const PAINT_DEFS: &[(&[&str], &[Paint])] = &[
     (&["marsh", "wet_meadow", "fen"], &[Paint::Fill(GRASSY), Paint::Pattern("wetland"), Paint::Pattern("marsh")]),
     (&["reedbed"], &[Paint::Fill(GRASSY), Paint::Pattern("wetland"), Paint::Pattern("reedbed")]),
     (&["scree"], &[Paint::Fill(SCREE), Paint::Pattern("scree")]),
+    (&["mud"], &[Paint::Fill(MUD), Paint::Pattern("mud")]),
+    (&["tidalflat"], &[Paint::Fill(TIDALFLAT), Paint::Pattern("tidalflat")]),
     (&["farmland"], &[Paint::Fill(FARMLAND)]),
     (&["farmyard"], &[Paint::Fill(FARMYARD), Paint::Stroke(2.0, BLACK)]),
     (&["beach"], &[Paint::Fill(BEACH), Paint::Pattern("sand")]),
@@ -61,6 +68,7 @@ pub const PAINT_DEFS: &[(&[&str], &[Paint])] = &[
 
     (&["parking"], &[Paint::Fill(PARKING), Paint::Stroke(2.0, PARKING_STROKE)]),
     (&["recreation_ground"], &[Paint::Fill(RECREATION_GROUND)]),
+    (&["marina"], &[Paint::Fill(MARINA), Paint::Stroke(2.0, WATER_LABEL)]),
     (&["winter_sports"], &[]), // NOTE handled separately
     (&["silo"], &[Paint::Fill(SILO), Paint::Stroke(2.0, SILO_STROKE)]),
 ];
@@ -77,9 +85,12 @@ pub static PAINTS: LazyLock<HashMap<&'static str, &'static [Paint]>> = LazyLock:
     paint_map
 });
 
+/// `min_areas` are per-type minimum polygon areas for the zoom, see
+/// [`crate::render::LandcoverMinAreas::for_zoom`].
 pub async fn query(
     ctx: &Ctx,
     client: &tokio_postgres::Client,
+    min_areas: &[(String, f64)],
 ) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
     let a = "'pitch', 'playground', 'golf_course', 'track'";
 
@@ -97,6 +108,23 @@ pub async fn query(
         12.. => "",
     };
 
+    let mut params = ctx.bbox_query_params(Some(4.0));
+
+    let min_area_cond = if min_areas.is_empty() {
+        String::new()
+    } else {
+        let mut whens = String::new();
+
+        // after the 5 bbox params
+        for (i, (typ, min_area)) in min_areas.iter().enumerate() {
+            whens.push_str(&format!(" WHEN ${} THEN ${}::float8", 6 + 2 * i, 7 + 2 * i));
+
+            params = params.push(typ.clone()).push(*min_area);
+        }
+
+        format!("area >= CASE type{whens} ELSE 0 END AND")
+    };
+
     let z_order_case = build_landcover_z_order_case("type");
 
     let query = &format!("
@@ -104,8 +132,9 @@ pub async fn query(
             CASE
                 WHEN
                     type = 'wetland' AND
-                    tags->'wetland' IN ('bog', 'reedbed', 'marsh', 'swamp', 'wet_meadow', 'mangrove', 'fen')
+                    tags->'wetland' IN ('bog', 'reedbed', 'marsh', 'swamp', 'wet_meadow', 'mangrove', 'fen', 'tidalflat')
                 THEN tags->'wetland'
+                WHEN type = 'mud' AND tags->'tidal' = 'yes' THEN 'tidalflat'
                 ELSE type
             END AS type,
             geometry,
@@ -115,15 +144,14 @@ pub async fn query(
             osm_landcovers{table_suffix}
         WHERE
             {excl_types}
+            {min_area_cond}
             geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
         ORDER BY
             z_order DESC NULLS LAST,
             osm_id
     ");
 
-    client
-        .query(query, &ctx.bbox_query_params(Some(4.0)).as_params())
-        .await
+    client.query(query, &params.as_params()).await
 }
 
 pub fn render(
@@ -131,13 +159,22 @@ pub fn render(
     context: &Context,
     rows: Vec<Feature>,
     svg_repo: &mut SvgRepo,
+    hillshading: Option<(&HillshadingHierarchy, &mut HillshadingDatasets)>,
+    hillshading_surfaces: &mut Option<TileSurfaces>,
+    blending: bool,
 ) -> LayerRenderResult {
     let _span = tracy_client::span!("landcover::render");
 
-    let min = ctx.bbox.min();
-
     let zoom = ctx.zoom;
 
+    let mut rock_geoms = Vec::new();
+
+    let blend = blending && zoom >= 14;
+
+    let mut scrub_geoms = Vec::new();
+
+    let mut grassy_geoms = Vec::new();
+
     context.save()?;
 
     for row in &rows {
@@ -149,6 +186,20 @@ pub fn render(
 
         let geom = row.get_geometry()?.project_to_tile(&ctx.tile_projector);
 
+        if hillshading.is_some() && zoom >= 13 && matches!(typ, "bare_rock" | "scree") {
+            rock_geoms.push(geom.clone());
+        }
+
+        if blend {
+            match typ {
+                "scrub" => scrub_geoms.push(geom.clone()),
+                "meadow" | "village_green" | "fell" | "grass" | "grassland" | "heath" => {
+                    grassy_geoms.push(geom.clone());
+                }
+                _ => {}
+            }
+        }
+
         if let Some(paints) = PAINTS.get(typ) {
             if paints.len() > 1 {
                 context.push_group();
@@ -162,21 +213,7 @@ pub fn render(
                         context.fill()?;
                     }
                     Paint::Pattern(pattern) => {
-                        let tile = svg_repo.get(pattern)?;
-
-                        let pattern = SurfacePattern::create(tile);
-
-                        let (x, y) = to_absolute_pixel_coords(min.x, min.y, zoom);
-
-                        let rect = tile.extents().expect("tile extents");
-
-                        let mut matrix = Matrix::identity();
-                        matrix.translate((x % rect.width()).round(), (y % rect.height()).round());
-                        pattern.set_matrix(matrix);
-
-                        pattern.set_extend(Extend::Repeat);
-
-                        context.set_source(&pattern)?;
+                        context.set_source(&tile_pattern(ctx, svg_repo.get(pattern)?))?;
 
                         path_geometry(context, &geom);
 
@@ -185,7 +222,7 @@ pub fn render(
                     Paint::Stroke(width, color) => {
                         if matches!(
                             typ,
-                            "garden" | "park" | "cemetery" | "dog_park" | "farmyard"
+                            "garden" | "park" | "cemetery" | "dog_park" | "farmyard" | "marina"
                         ) {
                             context.set_source_color_a(*color, 0.2);
                         } else {
@@ -237,6 +274,23 @@ pub fn render(
 
     context.restore()?;
 
+    if !scrub_geoms.is_empty() && !grassy_geoms.is_empty() {
+        render_scrub_transitions(ctx, context, &scrub_geoms, &grassy_geoms, svg_repo)?;
+    }
+
+    if let Some((hierarchy, hillshading_datasets)) = hillshading
+        && !rock_geoms.is_empty()
+    {
+        render_rock_shading(
+            ctx,
+            context,
+            &rock_geoms,
+            hierarchy,
+            hillshading_datasets,
+            hillshading_surfaces,
+        )?;
+    }
+
     if ctx.zoom >= 14 {
         context.push_group();
 
@@ -265,3 +319,197 @@ pub fn render(
 
     Ok(())
 }
+
+/// Lets scrub fade into the bordering grassland and heath in dithered bands of decreasing
+/// density instead of a hard edge.
+fn render_scrub_transitions(
+    ctx: &Ctx,
+    context: &Context,
+    scrub_geoms: &[Geometry],
+    grassy_geoms: &[Geometry],
+    svg_repo: &mut SvgRepo,
+) -> LayerRenderResult {
+    const BANDS: [(f64, f64); 3] = [(12.0, 0.25), (8.0, 0.5), (4.0, 0.75)];
+
+    let scrub_pattern = tile_pattern(ctx, svg_repo.get("scrub")?);
+
+    context.save()?;
+
+    for geom in grassy_geoms {
+        path_geometry(context, geom);
+    }
+
+    context.clip();
+
+    context.set_line_join(cairo::LineJoin::Round);
+
+    for (width, density) in BANDS {
+        context.push_group();
+
+        context.set_line_width(width);
+
+        for geom in scrub_geoms {
+            path_geometry(context, geom);
+        }
+
+        context.stroke()?;
+
+        context.set_operator(cairo::Operator::In);
+        context.set_source_color(SCRUB);
+        context.paint()?;
+
+        context.set_operator(cairo::Operator::Atop);
+        context.set_source(&scrub_pattern)?;
+        context.paint()?;
+
+        context.pop_group_to_source()?;
+
+        let mask = ImageSurface::create_for_data(dither_mask(density), Format::A8, 4, 4, 4)?;
+
+        let mask_pattern = SurfacePattern::create(&mask);
+        mask_pattern.set_extend(Extend::Repeat);
+        mask_pattern.set_filter(cairo::Filter::Nearest);
+
+        context.mask(&mask_pattern)?;
+    }
+
+    context.restore()?;
+
+    Ok(())
+}
+
+/// 4×4 ordered (Bayer) dither alpha mask with `density` of its cells opaque.
+fn dither_mask(density: f64) -> Vec<u8> {
+    const BAYER: [u8; 16] = [0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5];
+
+    BAYER
+        .iter()
+        .map(|threshold| {
+            if (f64::from(*threshold) + 0.5) / 16.0 < density {
+                255
+            } else {
+                0
+            }
+        })
+        .collect()
+}
+
+/// Multiplies hillshading into bare rock and scree so that steep faces read darker.
+///
+/// The loaded surfaces are kept in `surfaces` for the shading pass.
+fn render_rock_shading(
+    ctx: &Ctx,
+    context: &Context,
+    geoms: &[Geometry],
+    hierarchy: &HillshadingHierarchy,
+    hillshading_datasets: &mut HillshadingDatasets,
+    surfaces: &mut Option<TileSurfaces>,
+) -> LayerRenderResult {
+    if surfaces.is_none() {
+        *surfaces = Some(TileSurfaces::load_masks(
+            ctx,
+            hierarchy,
+            hillshading_datasets,
+        )?);
+    }
+
+    let surfaces = surfaces.as_mut().expect("tile surfaces loaded");
+
+    context.push_group(); // rock-shading
+
+    for entry in hierarchy.entries() {
+        if surfaces.mask(entry.country).is_none() {
+            continue;
+        }
+
+        surfaces.load_shading(ctx, entry.country, hillshading_datasets)?;
+
+        let (Some(mask_surface), Some(shading_surface)) = (
+            surfaces.mask(entry.country),
+            surfaces.shading(entry.country),
+        ) else {
+            continue;
+        };
+
+        context.push_group(); // country
+
+        hillshading::paint_surface(ctx, context, mask_surface, 1.0)?;
+
+        context.set_operator(cairo::Operator::In);
+        hillshading::paint_surface(ctx, context, shading_surface, 1.0)?;
+
+        for better in &entry.better {
+            if let Some(better_mask) = surfaces.mask(better) {
+                context.set_operator(cairo::Operator::DestOut);
+                hillshading::paint_surface(ctx, context, better_mask, 1.0)?;
+            }
+        }
+
+        context.pop_group_to_source()?; // country
+        context.paint()?;
+    }
+
+    context.pop_group_to_source()?; // rock-shading
+
+    context.save()?;
+
+    for geom in geoms {
+        path_geometry(context, geom);
+    }
+
+    context.clip();
+
+    context.set_operator(cairo::Operator::Multiply);
+    context.paint_with_alpha(0.5)?;
+
+    context.restore()?;
+
+    Ok(())
+}
+
+/// Repeating pattern of the tile aligned to the absolute pixel grid to stay seamless across tiles.
+pub(super) fn tile_pattern(ctx: &Ctx, tile: &RecordingSurface) -> SurfacePattern {
+    let min = ctx.bbox.min();
+
+    let pattern = SurfacePattern::create(tile);
+
+    let (x, y) = to_absolute_pixel_coords(min.x, min.y, ctx.zoom);
+
+    let rect = tile.extents().expect("tile extents");
+
+    let mut matrix = Matrix::identity();
+    matrix.translate((x % rect.width()).round(), (y % rect.height()).round());
+    pattern.set_matrix(matrix);
+
+    pattern.set_extend(Extend::Repeat);
+
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opaque_cells(density: f64) -> usize {
+        dither_mask(density)
+            .iter()
+            .filter(|alpha| **alpha == 255)
+            .count()
+    }
+
+    #[test]
+    fn dither_mask_density() {
+        assert_eq!(opaque_cells(0.0), 0);
+        assert_eq!(opaque_cells(0.25), 4);
+        assert_eq!(opaque_cells(0.5), 8);
+        assert_eq!(opaque_cells(1.0), 16);
+    }
+
+    #[test]
+    fn denser_dither_mask_covers_sparser() {
+        let sparse = dither_mask(0.25);
+        let dense = dither_mask(0.75);
+
+        assert!(sparse.iter().zip(&dense).all(|(s, d)| *s <= *d));
+    }
+}
@@ -54,7 +54,8 @@ pub async fn query(
                 osm_landcovers
             WHERE
                 type NOT IN ('zoo', 'theme_park', 'winter_sports', 'national_park',
-                    'protected_area', 'nature_reserve', 'aquaculture') AND
+                    'protected_area', 'nature_reserve', 'aquaculture', 'marina', 'golf_bunker',
+                    'golf_fairway', 'golf_green', 'golf_tee', 'sinkhole', 'reef') AND
                 area >= $6 AND
                 (name <> '' OR tags ? 'ref') AND
                 geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
@@ -62,6 +63,7 @@ pub async fn query(
         SELECT
             name,
             type,
+            ST_XMax(geometry) - ST_XMin(geometry) AS width,
             ST_PointOnSurface(geometry) AS geometry
         FROM
             main
@@ -105,6 +107,8 @@ pub fn render(
         ..TextOptions::default()
     };
 
+    let meters_per_pixel = ctx.meters_per_pixel();
+
     for row in rows {
         let typ = row.get_string("type")?;
 
@@ -134,7 +138,16 @@ pub fn render(
             colors::AREA_LABEL
         };
 
-        let g = match row.get_geometry()? {
+        let geometry = row.get_geometry()?;
+
+        // legend features come as polygons without the width column
+        let area_width = if natural && ctx.zoom <= 14 && matches!(geometry, Geometry::Point(_)) {
+            row.get_f64("width")? / meters_per_pixel
+        } else {
+            0.0
+        };
+
+        let g = match geometry {
             Geometry::Point(point) => point,
             Geometry::Polygon(polygon) => polygon.centroid().expect("centroid"),
             Geometry::MultiPolygon(mp) => mp.centroid().expect("centroid"),
@@ -151,14 +164,71 @@ pub fn render(
             }
         };
 
+        let name = replace(row.get_string("name")?, &REPLACEMENTS);
+
+        let letter_spacing = area_letter_spacing(&name, text_options.flo.size, area_width);
+
         draw_text(
             context,
             Some(collision),
             &g.project_to_tile(&ctx.tile_projector),
-            &replace(row.get_string("name")?, &REPLACEMENTS),
-            &text_options,
+            &name,
+            &TextOptions {
+                flo: FontAndLayoutOptions {
+                    letter_spacing,
+                    max_width: text_options.flo.max_width.max(area_width),
+                    ..text_options.flo
+                },
+                ..text_options
+            },
         )?;
     }
 
     Ok(())
 }
+
+/// Letter spacing (tracking) stretching a single-line label towards a part of the area width.
+/// Glyph advances are estimated from the font size as the text is not shaped yet.
+pub(super) fn area_letter_spacing(text: &str, size: f64, area_width: f64) -> f64 {
+    const SPAN_RATIO: f64 = 0.6;
+    const GLYPH_WIDTH_EMS: f64 = 0.55;
+    const MAX_SPACING_EMS: f64 = 1.5;
+
+    let char_count = text.chars().count();
+
+    if char_count < 2 {
+        return 0.0;
+    }
+
+    let text_width = char_count as f64 * size * GLYPH_WIDTH_EMS;
+
+    (area_width.mul_add(SPAN_RATIO, -text_width) / (char_count - 1) as f64)
+        .clamp(0.0, size * MAX_SPACING_EMS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letter_spacing_grows_with_area_width() {
+        let narrow = area_letter_spacing("Veľká Fatra", 12.0, 150.0);
+        let medium = area_letter_spacing("Veľká Fatra", 12.0, 250.0);
+        let wide = area_letter_spacing("Veľká Fatra", 12.0, 350.0);
+
+        assert!(narrow < medium);
+        assert!(medium < wide);
+    }
+
+    #[test]
+    fn letter_spacing_is_zero_for_small_areas() {
+        assert!(area_letter_spacing("Veľká Fatra", 12.0, 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn letter_spacing_is_capped() {
+        let spacing = area_letter_spacing("Les", 12.0, 10_000.0);
+
+        assert!((spacing - 18.0).abs() < f64::EPSILON);
+    }
+}
@@ -15,6 +15,7 @@ pub const LANDCOVER_Z_ORDER: &[&str] = &[
     "weir",
     "clearcut",
     "wetland",
+    "mud",
     "scrub",
     "orchard",
     "vineyard",
@@ -36,6 +37,7 @@ pub const LANDCOVER_Z_ORDER: &[&str] = &[
     "wood",
     "forest",
     "park",
+    "marina",
     "golf_course",
     "grassland",
     "farm",
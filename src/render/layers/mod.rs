@@ -1,11 +1,14 @@
 pub use crate::render::categories::Category;
+pub use debug_query::query_layer;
 pub use hillshading_datasets::HillshadingDatasets;
 pub use hillshading_datasets::load_hillshading_datasets;
+pub use hillshading_datasets::report_preloaded_datasets;
 pub use landcover::PAINT_DEFS;
+pub use pipeline::LAYER_NAMES;
 pub use pipeline::RenderError;
 pub use pipeline::Shading;
 pub use pipeline::render;
-pub use pois::{POI_ORDER, POIS};
+pub use pois::{PEAK_TIER_SQL, POI_ORDER, POIS};
 
 mod aerialway_names;
 mod blur_edges;
@@ -17,11 +20,14 @@ mod buildings;
 mod contours;
 mod country_names;
 mod custom;
+mod debug_query;
 mod decorations;
 mod embankments;
 mod feature_lines;
 mod fixmes;
 mod geonames;
+mod golf;
+mod highlight;
 mod highway_names;
 mod hillshading;
 mod hillshading_datasets;
@@ -33,15 +39,18 @@ mod locality_names;
 mod military_areas;
 mod national_park_names;
 mod pipeline;
+mod pitch_markings;
 mod place_names;
 mod poi_z_order;
 mod pois;
 mod power_towers_poles;
 mod protected_areas;
+mod reefs;
 mod road_access_restrictions;
 mod roads;
 mod routes;
 mod sea;
+mod seamarks;
 mod shading_and_contours;
 mod solar_power_plants;
 mod special_park_names;
@@ -52,3 +61,4 @@ mod water_area_names;
 mod water_areas;
 mod water_line_names;
 mod water_lines;
+mod winter_sports_names;
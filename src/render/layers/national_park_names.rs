@@ -5,9 +5,12 @@ use crate::render::{
     ctx::Ctx,
     draw::{
         font_options::FontAndLayoutOptions,
+        path_geom::walk_geometry_line_strings,
         text::{TextOptions, draw_text},
+        text_on_line::{Align, Distribution, Repeat, TextOnLineOptions, draw_text_on_line},
     },
     layer_render_error::LayerRenderResult,
+    layers::landcover_names::area_letter_spacing,
     projectable::TileProjectable,
     regex_replacer::{Replacement, replace},
 };
@@ -35,6 +38,7 @@ pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tok
             type,
             name,
             COALESCE(tags->'protect_class', '') AS protect_class,
+            ST_XMax(geometry) - ST_XMin(geometry) AS width,
             ST_PointOnSurface(geometry) AS geometry
         FROM
             osm_landcovers
@@ -51,6 +55,40 @@ pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tok
     client.query(sql, &ctx.bbox_query_params(Some(512.0)).as_params()).await
 }
 
+/// Boundaries of the parks, simplified so that the repeated names can follow them.
+pub async fn query_borders(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    // buffer zones would double the labels along the park boundaries
+    let sql = "
+        SELECT
+            name,
+            ST_Simplify(ST_Boundary(geometry), $6) AS geometry
+        FROM
+            osm_landcovers
+        WHERE
+            name <> '' AND
+            name NOT LIKE ('Ochranné pásmo %') AND
+            geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5) AND
+            (type = 'national_park' OR (type = 'protected_area' AND tags->'protect_class' = '2'))
+        ORDER BY
+            area DESC,
+            osm_id
+    ";
+
+    let meters_per_pixel = ctx.meters_per_pixel();
+
+    client
+        .query(
+            sql,
+            &ctx.bbox_query_params(Some(1024.0))
+                .push(2.0 * meters_per_pixel)
+                .as_params(),
+        )
+        .await
+}
+
 pub fn render(
     ctx: &Ctx,
     context: &Context,
@@ -69,15 +107,69 @@ pub fn render(
         ..TextOptions::default()
     };
 
+    let meters_per_pixel = ctx.meters_per_pixel();
+
     for row in rows {
+        let name = replace(row.get_string("name")?, &REPLACEMENTS);
+
+        let area_width = row.get_f64("width")? / meters_per_pixel;
+
         draw_text(
             context,
             Some(collision),
             &row.get_point()?.project_to_tile(&ctx.tile_projector),
-            &replace(row.get_string("name")?, &REPLACEMENTS),
-            &text_options,
+            &name,
+            &TextOptions {
+                flo: FontAndLayoutOptions {
+                    letter_spacing: area_letter_spacing(&name, text_options.flo.size, area_width),
+                    max_width: text_options.flo.max_width.max(area_width),
+                    ..text_options.flo
+                },
+                ..text_options
+            },
         )?;
     }
 
     Ok(())
 }
+
+/// Repeats the names along the park boundaries, complementing the centered labels on views
+/// showing only a part of a large park.
+pub fn render_borders(
+    ctx: &Ctx,
+    context: &Context,
+    rows: Vec<Feature>,
+    collision: &mut Collision,
+) -> LayerRenderResult {
+    let _span = tracy_client::span!("national_park_names::render_borders");
+
+    let text_options = TextOnLineOptions {
+        flo: FontAndLayoutOptions {
+            style: Style::Italic,
+            ..FontAndLayoutOptions::default()
+        },
+        color: colors::PROTECTED,
+        alpha: 0.66,
+        offset: -10.0,
+        keep_offset_side: true,
+        distribution: Distribution::Align {
+            align: Align::Center,
+            repeat: Repeat::Spaced(800.0),
+        },
+        ..TextOnLineOptions::default()
+    };
+
+    for row in rows {
+        let name = replace(row.get_string("name")?, &REPLACEMENTS);
+
+        let geom = row.get_geometry()?.project_to_tile(&ctx.tile_projector);
+
+        walk_geometry_line_strings(&geom, &mut |geom| {
+            let _drawn = draw_text_on_line(context, geom, &name, Some(collision), &text_options)?;
+
+            cairo::Result::Ok(())
+        })?;
+    }
+
+    Ok(())
+}
@@ -1,14 +1,15 @@
 use crate::render::colors::ContextExt;
 use crate::render::projectable::TileProjectable;
-use crate::render::render_request::CustomLayer;
+use crate::render::render_request::{CustomLayer, Highlight};
 use crate::render::{
-    ContourCountries, CustomLayerOrder, HillshadingHierarchy, RenderLayer, colors,
+    ContourCountries, ContourIntervals, CustomLayerOrder, HillshadingHierarchy, LandcoverMinAreas,
+    RenderLayer, colors,
 };
 use crate::render::{
     Feature, ImageFormat,
     collision::Collision,
     ctx::Ctx,
-    layer_render_error::{LayerRenderError, LayerRenderResult},
+    layer_render_error::{LayerRenderError, LayerRenderResult, is_connection_error},
     layers,
     layers::hillshading_datasets::HillshadingDatasets,
     projectable::TileProjector,
@@ -21,7 +22,7 @@ use deadpool_postgres::Pool;
 use futures_util::FutureExt;
 use futures_util::future::BoxFuture;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::sync::Arc;
 use thiserror::Error;
@@ -52,6 +53,11 @@ impl RenderError {
     pub const fn new(layer: &'static str, source: LayerRenderError) -> Self {
         Self::Layer { layer, source }
     }
+
+    /// Whether a layer query failed on a broken DB connection.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, Self::Layer { source, .. } if source.is_connection_error())
+    }
 }
 
 trait WithLayer<T> {
@@ -93,19 +99,93 @@ enum PendingLayer<'a> {
     },
 }
 
+/// Layer names accepted by `--disable-layer`: names of the queried layers and their legend
+/// groups. Disabling a group disables all its layers.
+pub const LAYER_NAMES: &[&str] = &[
+    "aerialway_names",
+    "bordered_area_names_borders",
+    "bordered_area_names_centroids",
+    "borders",
+    "bridge_areas",
+    "bridge_for_shading",
+    "building_names",
+    "buildings",
+    "contours",
+    "contours_fallback",
+    "country_borders",
+    "country_names",
+    "embankment_top_paths",
+    "embankments",
+    "feature_lines",
+    "feature_lines_1",
+    "feature_lines_2",
+    "feature_lines_3",
+    "feature_lines_4",
+    "fixmes",
+    "fixmes_line",
+    "fixmes_points",
+    "geonames",
+    "golf",
+    "highlight",
+    "highway_names",
+    "housenumbers",
+    "landcover_names",
+    "landcovers",
+    "locality_names",
+    "military_areas",
+    "national_park_names",
+    "national_park_names_borders",
+    "pitch_markings",
+    "place_names",
+    "place_names_highzoom",
+    "poi_icons",
+    "pois",
+    "power_towers_poles",
+    "protected_areas",
+    "protected_areas_areas",
+    "protected_areas_borders",
+    "reefs",
+    "ridges",
+    "road_access_restrictions",
+    "roads",
+    "routes",
+    "routes_labels",
+    "routes_marking",
+    "sea",
+    "seamarks",
+    "solar_power_plants",
+    "special_park_names",
+    "special_parks",
+    "trees",
+    "valleys",
+    "valleys_ridges",
+    "water_area_names",
+    "water_areas",
+    "water_line_names",
+    "water_lines",
+    "winter_sports_names",
+];
+
 struct Prefetcher<'a> {
     pool: Pool,
     handle: Handle,
     ctx: Arc<Ctx>,
+    disabled_layers: &'a HashSet<String>,
     layers: Vec<PendingLayer<'a>>,
 }
 
 impl<'a> Prefetcher<'a> {
-    const fn new(pool: Pool, handle: Handle, ctx: Arc<Ctx>) -> Self {
+    const fn new(
+        pool: Pool,
+        handle: Handle,
+        ctx: Arc<Ctx>,
+        disabled_layers: &'a HashSet<String>,
+    ) -> Self {
         Self {
             pool,
             handle,
             ctx,
+            disabled_layers,
             layers: Vec::new(),
         }
     }
@@ -113,18 +193,30 @@ impl<'a> Prefetcher<'a> {
     /// Add a layer with a DB query.
     /// Each query spawns its own tokio task with its own pool connection,
     /// so all queries run in parallel on the DB.
+    /// A connection broken during the query is detached from the pool instead of being returned.
     fn add(
         &mut self,
         name: &'static str,
         legend_name: Option<&'static str>,
         query_fn: impl FnOnce(
             Arc<Ctx>,
-            deadpool_postgres::Object,
+            Arc<deadpool_postgres::Object>,
         ) -> BoxFuture<'static, Result<Vec<Row>, tokio_postgres::Error>>
         + Send
         + 'static,
         render_fn: impl FnOnce(Vec<Feature>, Params) -> LayerRenderResult + 'a,
     ) {
+        debug_assert!(
+            LAYER_NAMES.contains(&name) || legend_name.is_some_and(|l| LAYER_NAMES.contains(&l)),
+            "unknown layer {name}"
+        );
+
+        if self.disabled_layers.contains(name)
+            || legend_name.is_some_and(|legend_name| self.disabled_layers.contains(legend_name))
+        {
+            return;
+        }
+
         if let Some(ref legend) = self.ctx.legend {
             let key = legend_name.unwrap_or(name);
 
@@ -148,8 +240,22 @@ impl<'a> Prefetcher<'a> {
         let ctx = self.ctx.clone();
 
         let jh = self.handle.spawn(async move {
-            let conn = pool.get().await.map_err(LayerRenderError::from)?;
-            let rows = query_fn(ctx, conn).await.map_err(LayerRenderError::from)?;
+            let conn = Arc::new(pool.get().await.map_err(LayerRenderError::from)?);
+
+            let rows = match query_fn(ctx, conn.clone()).await {
+                Ok(rows) => rows,
+                Err(err) => {
+                    if is_connection_error(&err)
+                        && let Ok(conn) = Arc::try_unwrap(conn)
+                    {
+                        // closes the client rather than handing it to the next query
+                        drop(deadpool_postgres::Object::take(conn));
+                    }
+
+                    return Err(LayerRenderError::from(err));
+                }
+            };
+
             Ok::<Vec<Feature>, LayerRenderError>(rows.into_iter().map(Feature::from).collect())
         });
 
@@ -215,9 +321,11 @@ impl<'a> Prefetcher<'a> {
 pub struct Shading<'a> {
     pub hierarchy: Option<&'a HillshadingHierarchy>,
     pub contour_countries: Option<&'a ContourCountries>,
+    pub contour_intervals: &'a ContourIntervals,
     pub datasets: Option<&'a mut HillshadingDatasets>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     surface: &Surface,
     request: &RenderRequest,
@@ -226,6 +334,8 @@ pub fn render(
     handle: Handle,
     size: Size<u32>,
     svg_repo: &mut SvgRepo,
+    disabled_layers: &HashSet<String>,
+    landcover_min_areas: &LandcoverMinAreas,
 ) -> Result<(), RenderError> {
     let _span = tracy_client::span!("render_tile::draw");
 
@@ -275,7 +385,13 @@ pub fn render(
         None
     };
 
-    let mut prefetcher = Prefetcher::new(pool, handle, ctx.clone());
+    let mut prefetcher = Prefetcher::new(pool, handle, ctx.clone(), disabled_layers);
+
+    let maritime_borders = to_render.contains(&RenderLayer::MaritimeBorders);
+
+    let landcover_blending = to_render.contains(&RenderLayer::LandcoverBlending);
+
+    let roof_shading = zoom >= 19 && to_render.contains(&RenderLayer::RoofShading);
 
     if request.legend.is_none() {
         prefetcher.add(
@@ -286,12 +402,55 @@ pub fn render(
         );
     }
 
-    prefetcher.add(
-        "landcovers",
-        None,
-        |ctx, conn| async move { layers::landcover::query(&ctx, &conn).await }.boxed(),
-        |rows, params| layers::landcover::render(&ctx, context, rows, params.svg_repo),
-    );
+    let landcover_min_areas = landcover_min_areas.for_zoom(zoom);
+
+    // hillshading surfaces loaded by the rock shading of landcovers, reused by the shading pass
+    let hillshading_surfaces: Rc<RefCell<Option<layers::hillshading::TileSurfaces>>> =
+        Rc::new(RefCell::new(None));
+
+    {
+        let ctx = ctx.clone();
+        let hierarchy = shading.hierarchy;
+        let hillshading_surfaces = hillshading_surfaces.clone();
+
+        prefetcher.add(
+            "landcovers",
+            None,
+            move |ctx, conn| {
+                async move { layers::landcover::query(&ctx, &conn, &landcover_min_areas).await }
+                    .boxed()
+            },
+            move |rows, params| {
+                layers::landcover::render(
+                    &ctx,
+                    context,
+                    rows,
+                    params.svg_repo,
+                    hierarchy.zip(do_shading.then_some(params.hsd).flatten()),
+                    &mut hillshading_surfaces.borrow_mut(),
+                    landcover_blending,
+                )
+            },
+        );
+    }
+
+    if zoom >= 17 {
+        prefetcher.add(
+            "golf",
+            None,
+            |ctx, conn| async move { layers::golf::query(&ctx, &conn).await }.boxed(),
+            |rows, _params| layers::golf::render(&ctx, context, rows),
+        );
+    }
+
+    if zoom >= 18 {
+        prefetcher.add(
+            "pitch_markings",
+            None,
+            |ctx, conn| async move { layers::pitch_markings::query(&ctx, &conn).await }.boxed(),
+            |rows, _params| layers::pitch_markings::render(&ctx, context, rows),
+        );
+    }
 
     // feature_lines is queried per render stage (up to 4×). All tasks run in parallel
     // so the cost is a pool connection rather than a round-trip per query.
@@ -320,6 +479,15 @@ pub fn render(
         |rows, _params| layers::water_areas::render(&ctx, context, rows),
     );
 
+    if zoom >= 15 {
+        prefetcher.add(
+            "reefs",
+            None,
+            |ctx, conn| async move { layers::reefs::query(&ctx, &conn).await }.boxed(),
+            |rows, params| layers::reefs::render(&ctx, context, rows, params.svg_repo),
+        );
+    }
+
     if zoom >= 15 {
         prefetcher.add(
             "bridge_areas",
@@ -356,6 +524,17 @@ pub fn render(
         );
     }
 
+    if zoom >= 15 {
+        prefetcher.add(
+            "embankment_top_paths",
+            None,
+            |ctx, conn| {
+                async move { layers::embankments::query_top_paths(&ctx, &conn).await }.boxed()
+            },
+            |rows, _params| layers::embankments::render_top_paths(&ctx, context, rows),
+        );
+    }
+
     if zoom >= 16 {
         prefetcher.add(
             "embankments",
@@ -424,6 +603,9 @@ pub fn render(
             && zoom >= 12
             && let Some(contour_countries) = shading.contour_countries
         {
+            let interval = shading.contour_intervals.interval(zoom);
+            let unit = shading.contour_intervals.unit();
+
             for entry in contour_countries.entries() {
                 let acc = results.clone();
 
@@ -433,8 +615,11 @@ pub fn render(
                     entry.layer_name,
                     Some("contours"),
                     move |ctx, conn| {
-                        async move { layers::contours::query(&ctx, &conn, Some(country)).await }
-                            .boxed()
+                        async move {
+                            layers::contours::query(&ctx, &conn, Some(country), interval, unit)
+                                .await
+                        }
+                        .boxed()
                     },
                     move |features, _params| {
                         acc.lock()
@@ -451,8 +636,11 @@ pub fn render(
                 prefetcher.add(
                     "contours_fallback",
                     Some("contours"),
-                    |ctx, conn| {
-                        async move { layers::contours::query(&ctx, &conn, None).await }.boxed()
+                    move |ctx, conn| {
+                        async move {
+                            layers::contours::query(&ctx, &conn, None, interval, unit).await
+                        }
+                        .boxed()
                     },
                     move |features, _params| {
                         acc.lock()
@@ -523,6 +711,7 @@ pub fn render(
                     hierarchy: &hierarchy,
                     contour_countries: contour_countries_for_render.as_ref(),
                     do_shading,
+                    surfaces: hillshading_surfaces.borrow_mut().take(),
                 },
             )
             .with_layer("shading_and_contours")?;
@@ -544,8 +733,10 @@ pub fn render(
         prefetcher.add(
             "buildings",
             None,
-            |ctx, conn| async move { layers::buildings::query(&ctx, &conn).await }.boxed(),
-            |rows, _params| layers::buildings::render(&ctx, context, rows),
+            |ctx, conn| {
+                async move { layers::buildings::query(&ctx, &conn, roof_shading).await }.boxed()
+            },
+            |rows, _params| layers::buildings::render(&ctx, context, rows, roof_shading),
         );
     }
 
@@ -613,7 +804,7 @@ pub fn render(
             "borders",
             Some("country_borders"),
             |ctx, conn| async move { layers::borders::query(&ctx, &conn).await }.boxed(),
-            |rows, _params| layers::borders::render(&ctx, context, rows),
+            |rows, _params| layers::borders::render(&ctx, context, rows, maritime_borders),
         );
     }
 
@@ -795,6 +986,25 @@ pub fn render(
                 layers::national_park_names::render(&ctx, context, rows, params.collision)
             },
         );
+
+        if to_render.contains(&RenderLayer::NationalParkBorderNames) {
+            prefetcher.add(
+                "national_park_names_borders",
+                None,
+                |ctx, conn| {
+                    async move { layers::national_park_names::query_borders(&ctx, &conn).await }
+                        .boxed()
+                },
+                |rows, params| {
+                    layers::national_park_names::render_borders(
+                        &ctx,
+                        context,
+                        rows,
+                        params.collision,
+                    )
+                },
+            );
+        }
     }
 
     if (13..=16).contains(&zoom) {
@@ -808,18 +1018,45 @@ pub fn render(
         );
     }
 
+    if zoom >= 13 {
+        prefetcher.add(
+            "winter_sports_names",
+            None,
+            |ctx, conn| {
+                async move { layers::winter_sports_names::query(&ctx, &conn).await }.boxed()
+            },
+            |rows, params| {
+                layers::winter_sports_names::render(
+                    &ctx,
+                    context,
+                    rows,
+                    params.collision,
+                    params.svg_repo,
+                )
+            },
+        );
+    }
+
     let pois_to_label_slot: Rc<RefCell<Option<layers::pois::ToLabel>>> =
         Rc::new(RefCell::new(None));
 
     if zoom >= 10 {
         let kst = to_render.contains(&RenderLayer::RoutesHikingKst);
+        let mark_linked = to_render.contains(&RenderLayer::DebugWikidata);
         let slot_icons = pois_to_label_slot.clone();
         let ctx = ctx.clone();
+        let poi_filter = request.poi_filter.clone();
+        let query_poi_filter = poi_filter.clone();
 
         prefetcher.add(
             "poi_icons",
             Some("pois"),
-            move |ctx, conn| async move { layers::pois::query(&ctx, &conn, kst).await }.boxed(),
+            move |ctx, conn| {
+                async move {
+                    layers::pois::query(&ctx, &conn, kst, query_poi_filter.as_deref()).await
+                }
+                .boxed()
+            },
             move |rows, params| {
                 let to_label = layers::pois::render_icons(
                     &ctx,
@@ -827,6 +1064,8 @@ pub fn render(
                     rows,
                     params.collision,
                     params.svg_repo,
+                    poi_filter.as_deref(),
+                    mark_linked,
                 )?;
 
                 *slot_icons.borrow_mut() = Some(to_label);
@@ -847,6 +1086,15 @@ pub fn render(
         });
     }
 
+    if zoom >= 15 && to_render.contains(&RenderLayer::Seamarks) {
+        prefetcher.add(
+            "seamarks",
+            None,
+            |ctx, conn| async move { layers::seamarks::query(&ctx, &conn).await }.boxed(),
+            |rows, params| layers::seamarks::render(&ctx, context, rows, params.collision),
+        );
+    }
+
     if zoom >= 10 {
         prefetcher.add(
             "water_area_names",
@@ -866,27 +1114,54 @@ pub fn render(
     }
 
     if zoom >= 12 {
-        prefetcher.add(
-            "bordered_area_names_centroids",
-            Some("protected_areas"),
-            |ctx, conn| {
-                async move { layers::bordered_area_names::query_centroids(&ctx, &conn).await }
-                    .boxed()
-            },
-            |rows, params| {
-                layers::bordered_area_names::render_centroids(&ctx, context, rows, params.collision)
-            },
-        );
-        prefetcher.add(
-            "bordered_area_names_borders",
-            Some("protected_areas"),
-            |ctx, conn| {
-                async move { layers::bordered_area_names::query_borders(&ctx, &conn).await }.boxed()
-            },
-            |rows, params| {
-                layers::bordered_area_names::render_borders(&ctx, context, rows, params.collision)
-            },
-        );
+        // names labeled along national park borders take precedence over the centroid labels
+        let labeled_area_names = Rc::new(RefCell::new(HashSet::new()));
+
+        {
+            let ctx = ctx.clone();
+            let labeled_area_names = labeled_area_names.clone();
+
+            prefetcher.add(
+                "bordered_area_names_borders",
+                Some("protected_areas"),
+                |ctx, conn| {
+                    async move { layers::bordered_area_names::query_borders(&ctx, &conn).await }
+                        .boxed()
+                },
+                move |rows, params| {
+                    layers::bordered_area_names::render_borders(
+                        &ctx,
+                        context,
+                        rows,
+                        params.collision,
+                        &mut labeled_area_names.borrow_mut(),
+                    )
+                },
+            );
+        }
+
+        {
+            let ctx = ctx.clone();
+
+            prefetcher.add(
+                "bordered_area_names_centroids",
+                Some("protected_areas"),
+                |ctx, conn| {
+                    async move { layers::bordered_area_names::query_centroids(&ctx, &conn).await }
+                        .boxed()
+                },
+                move |rows, params| {
+                    layers::bordered_area_names::render_centroids(
+                        &ctx,
+                        context,
+                        rows,
+                        params.collision,
+                        &mut labeled_area_names.borrow_mut(),
+                    )
+                },
+            );
+        }
+
         prefetcher.add(
             "landcover_names",
             Some("landcovers"),
@@ -981,7 +1256,7 @@ pub fn render(
             "country_borders",
             None,
             |ctx, conn| async move { layers::borders::query(&ctx, &conn).await }.boxed(),
-            |rows, _params| layers::borders::render(&ctx, context, rows),
+            |rows, _params| layers::borders::render(&ctx, context, rows, maritime_borders),
         );
 
         prefetcher.add(
@@ -1069,6 +1344,19 @@ pub fn render(
         }
     }
 
+    if let Some(Highlight(osm_ids)) = &request.highlight {
+        let osm_ids = osm_ids.clone();
+
+        prefetcher.add(
+            "highlight",
+            None,
+            |ctx, conn| {
+                async move { layers::highlight::query(&ctx, &conn, &osm_ids).await }.boxed()
+            },
+            |rows, _params| layers::highlight::render(&ctx, context, rows),
+        );
+    }
+
     let collision = &mut Collision::new(Some(context));
 
     prefetcher.run(svg_repo, shading.datasets.as_deref_mut(), collision)?;
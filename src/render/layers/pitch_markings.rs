@@ -0,0 +1,161 @@
+use crate::render::{
+    Feature,
+    colors::{self, ContextExt},
+    ctx::Ctx,
+    layer_render_error::LayerRenderResult,
+    projectable::TileProjectable,
+};
+use cairo::{Context, Matrix};
+use geo::MinimumRotatedRect;
+use std::f64::consts::PI;
+
+/// Pitches shorter than this (in pixels) are too small for readable markings.
+const MIN_LENGTH: f64 = 16.0;
+
+pub async fn query(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    let sql = "
+        SELECT
+            tags->'sport' AS sport,
+            geometry
+        FROM
+            osm_landcovers
+        WHERE
+            type = 'pitch' AND
+            tags->'sport' IN ('soccer', 'basketball', 'tennis') AND
+            geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
+        ORDER BY
+            osm_id
+    ";
+
+    client
+        .query(sql, &ctx.bbox_query_params(Some(2.0)).as_params())
+        .await
+}
+
+/// Draws the court or field lines of the sport into the minimum rotated rectangle of the pitch,
+/// proportionally to the regulation dimensions.
+pub fn render(ctx: &Ctx, context: &Context, rows: Vec<Feature>) -> LayerRenderResult {
+    let _span = tracy_client::span!("pitch_markings::render");
+
+    context.save()?;
+
+    context.set_source_color_a(colors::WHITE, 0.8);
+    context.set_line_width(1.0);
+    context.set_dash(&[], 0.0);
+
+    for row in rows {
+        let sport = row.get_string("sport")?;
+
+        let geom = row.get_geometry()?.project_to_tile(&ctx.tile_projector);
+
+        let Some(rect) = geom.minimum_rotated_rect() else {
+            continue;
+        };
+
+        let &[c0, c1, _, c3, ..] = rect.exterior().0.as_slice() else {
+            continue;
+        };
+
+        let (a, b) = (c1 - c0, c3 - c0);
+
+        let (a_len, b_len) = (a.x.hypot(a.y), b.x.hypot(b.y));
+
+        // the local x axis runs along the pitch
+        let ((u, l), (v, w)) = if a_len >= b_len {
+            ((a, a_len), (b, b_len))
+        } else {
+            ((b, b_len), (a, a_len))
+        };
+
+        if l < MIN_LENGTH || w == 0.0 {
+            continue;
+        }
+
+        context.save()?;
+
+        context.transform(Matrix::new(u.x / l, u.y / l, v.x / w, v.y / w, c0.x, c0.y));
+
+        context.rectangle(0.0, 0.0, l, w);
+
+        match sport {
+            "soccer" => soccer(context, l, w),
+            "basketball" => basketball(context, l, w),
+            "tennis" => tennis(context, l, w),
+            _ => (),
+        }
+
+        // stroked in the tile space so that the lines keep their width
+        context.restore()?;
+
+        context.stroke()?;
+    }
+
+    context.restore()?;
+
+    Ok(())
+}
+
+fn line(context: &Context, x1: f64, y1: f64, x2: f64, y2: f64) {
+    context.move_to(x1, y1);
+    context.line_to(x2, y2);
+}
+
+fn circle(context: &Context, x: f64, y: f64, r: f64) {
+    context.new_sub_path();
+    context.arc(x, y, r, 0.0, 2.0 * PI);
+}
+
+/// 105 × 68 m field: halfway line, center circle and penalty areas.
+fn soccer(context: &Context, l: f64, w: f64) {
+    line(context, l / 2.0, 0.0, l / 2.0, w);
+
+    circle(context, l / 2.0, w / 2.0, (9.15 / 105.0 * l).min(w / 2.0));
+
+    let (depth, width) = (16.5 / 105.0 * l, 40.3 / 68.0 * w);
+
+    let top = (w - width) / 2.0;
+
+    context.rectangle(0.0, top, depth, width);
+    context.rectangle(l - depth, top, depth, width);
+}
+
+/// 28 × 15 m court: halfway line, center circle and the keys with free throw circles.
+fn basketball(context: &Context, l: f64, w: f64) {
+    line(context, l / 2.0, 0.0, l / 2.0, w);
+
+    let r = (1.8 / 28.0 * l).min(w / 2.0);
+
+    circle(context, l / 2.0, w / 2.0, r);
+
+    let (depth, width) = (5.8 / 28.0 * l, 4.9 / 15.0 * w);
+
+    let top = (w - width) / 2.0;
+
+    context.rectangle(0.0, top, depth, width);
+    context.rectangle(l - depth, top, depth, width);
+
+    circle(context, depth, w / 2.0, r);
+    circle(context, l - depth, w / 2.0, r);
+}
+
+/// 23.77 × 10.97 m doubles court: singles sidelines, net, service lines and center service line.
+fn tennis(context: &Context, l: f64, w: f64) {
+    let alley = 1.37 / 10.97 * w;
+
+    line(context, 0.0, alley, l, alley);
+    line(context, 0.0, w - alley, l, w - alley);
+
+    line(context, l / 2.0, 0.0, l / 2.0, w);
+
+    let service = 6.40 / 23.77 * l;
+
+    let (near, far) = (l / 2.0 - service, l / 2.0 + service);
+
+    line(context, near, alley, near, w - alley);
+    line(context, far, alley, far, w - alley);
+
+    line(context, near, w / 2.0, far, w / 2.0);
+}
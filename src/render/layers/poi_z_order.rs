@@ -6,8 +6,11 @@ const POI_Z_ORDER: &[&str] = &[
     "tower_observation",
     "cave_entrance",
     "arch",
+    "mountain_rescue",
+    "ranger_station",
     "office",
     "water_park",
+    "marina",
     "hotel",
     "chalet",
     "hostel",
@@ -19,6 +22,8 @@ const POI_Z_ORDER: &[&str] = &[
     "basic_hut",
     "camp_site",
     "castle",
+    "fort",
+    "city_gate",
     "manor",
     "forester's_lodge",
     "guidepost",
@@ -43,6 +48,8 @@ const POI_Z_ORDER: &[&str] = &[
     "greengrocer",
     "convenience",
     "supermarket",
+    "services",
+    "rest_area",
     "fuel",
     "fast_food",
     "cafe",
@@ -58,10 +65,14 @@ const POI_Z_ORDER: &[&str] = &[
     "soccer",
     "tennis",
     "basketball",
+    "multisport",
     "ice_skating",
     "running",
     "athletics",
     "swimming",
+    "boat_rental",
+    "slipway",
+    "canoe_put_in",
     "waterfall",
     "dam",
     "weir",
@@ -82,6 +93,7 @@ const POI_Z_ORDER: &[&str] = &[
     "disused_mine",
     "townhall",
     "memorial",
+    "tomb",
     "university",
     "college",
     "school",
@@ -89,7 +101,10 @@ const POI_Z_ORDER: &[&str] = &[
     "community_centre",
     "fire_station",
     "police",
+    "border_control",
     "post_office",
+    "bank",
+    "atm",
     "horse_racing",
     "horse_riding",
     "equestrian",
@@ -105,29 +120,50 @@ const POI_Z_ORDER: &[&str] = &[
     "tower_bell_tower",
     "tree_protected",
     "bicycle",
+    "bicycle_repair_station",
+    "compressed_air",
     "board",
     "map",
+    "survey_point",
     "artwork",
+    "artwork_statue",
+    "artwork_sculpture",
+    "artwork_mural",
+    "artwork_installation",
     "fountain",
     "playground",
+    "fitness_station",
     "wayside_shrine",
     "cross",
     "wayside_cross",
     "tree_shrine",
     "rock",
     "stone",
+    "underwater_rock",
     "sinkhole",
     "toilets",
+    "shower",
+    "sanitary_dump_station",
     "post_box",
     "telephone",
+    "elevator",
+    "vending_machine",
+    "vending_machine_drinks",
+    "vending_machine_food",
+    "vending_machine_public_transport_tickets",
     "chimney",
     "water_tower",
     "attraction",
     "sauna",
+    "spa",
+    "kneipp_water_cure",
     "tower_communication",
     "mast_communication",
     "tower_other",
     "mast_other",
+    "volcano1",
+    "volcano2",
+    "volcano3",
     "volcano",
     "peak1",
     "peak2",
@@ -142,6 +178,9 @@ const POI_Z_ORDER: &[&str] = &[
     "outdoor_seating",
     "parking",
     "firepit",
+    "fire_hydrant",
+    "water_tank",
+    "suction_point",
     "bench",
     "beehive",
     "apiary",
@@ -1,9 +1,9 @@
 use super::poi_z_order::build_poi_z_order_case;
 use crate::render::{
-    Feature,
+    Feature, PoiFilter,
     categories::Category,
     collision::Collision,
-    colors::{self, Color},
+    colors::{self, Color, ContextExt},
     ctx::Ctx,
     draw::{
         font_options::FontAndLayoutOptions,
@@ -116,11 +116,15 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
             replacements: build_replacements(&[(r"^[Ll]etisko\b *", "")]),
             ..Extra::default()
         }),
+        (13, 15, N, N, Poi, "services", Extra::default()),
+        (13, 15, N, N, Poi, "rest_area", Extra::default()),
         // (12, 12, Y, N, "guidepost", Extra { icon: Some("guidepost_x"), weight: Weight::BOLD, max_zoom: 12, ..Extra::default() }),
         (13, 13, Y, N, Poi, "guidepost", Extra { icon: Some("guidepost_xx"), weight: Weight::BOLD, max_zoom: 13, ..Extra::default() }),
         (14, 14, Y, N, Poi, "guidepost", Extra { icon: Some("guidepost_xx"), weight: Weight::BOLD, ..Extra::default() }),
-        // hex, not hsl(): older librsvg versions (e.g. on the production server) silently drop hsl() in user stylesheets, leaving the icon black
-        (10, 10, Y, Y, NaturalPoi, "volcano", Extra { icon: Some("peak"), font_size: 13.0, halo: false, text_color: colors::MILITARY, stylesheet: Some("path { fill: #c30404 }"), ..Extra::default() }),
+        (10, 10, Y, Y, NaturalPoi, "volcano1", Extra { icon: Some("volcano"), font_size: 13.0, halo: false, text_color: colors::MILITARY, ..Extra::default() }),
+        (11, 11, Y, Y, NaturalPoi, "volcano2", Extra { icon: Some("volcano"), font_size: 13.0, halo: false, text_color: colors::MILITARY, ..Extra::default() }),
+        (12, 12, Y, Y, NaturalPoi, "volcano3", Extra { icon: Some("volcano"), font_size: 13.0, halo: false, text_color: colors::MILITARY, ..Extra::default() }),
+        (13, 13, Y, Y, NaturalPoi, "volcano", Extra { font_size: 13.0, halo: false, text_color: colors::MILITARY, ..Extra::default() }),
         (10, 10, Y, Y, NaturalPoi, "peak1", Extra { icon: Some("peak"), font_size: 13.0, halo: false, ..Extra::default() }),
         (11, 11, Y, Y, NaturalPoi, "peak2", Extra { icon: Some("peak"), font_size: 13.0, halo: false, ..Extra::default() }),
         (12, 12, Y, Y, NaturalPoi, "peak3", Extra { icon: Some("peak"), font_size: 13.0, halo: false, ..Extra::default() }),
@@ -129,6 +133,7 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
             replacements: build_replacements(&[(r"^[Hh]rad\b *", "")]),
             ..Extra::default()
         }),
+        (14, 15, N, N, Poi, "fort", Extra::default()),
         (14, 15, Y, Y, NaturalPoi, "arch", Extra::default()),
         (14, 15, Y, Y, NaturalPoi, "cave_entrance", Extra {
             replacements: build_replacements(&[
@@ -148,7 +153,7 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
             ..Extra::default()
         }),
         (14, 15, N, N, Water, "drinking_water", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
-        (14, 15, N, N, Water, "water_point", Extra { text_color: colors::WATER_LABEL, icon: Some("drinking_water"), ..Extra::default() }),
+        (14, 15, N, N, Water, "water_point", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (14, 15, N, N, Water, "water_well", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (14, 15, Y, N, Poi, "monument", Extra::default()),
         (14, 15, Y, Y, Poi, "viewpoint", Extra {
@@ -208,7 +213,10 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
         (14, 15, N, N, Railway, "halt", Extra { icon: Some("station"), ..Extra::default() }),
         (14, 15, N, N, Poi, "bus_station", Extra::default()),
         (14, 15, N, N, Poi, "water_park", Extra::default()),
+        (14, 15, N, N, Water, "marina", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (14, 15, N, N, Institution, "museum", Extra::default()),
+        (14, 15, N, N, Institution, "ranger_station", Extra::default()),
+        (14, 15, N, N, Institution, "mountain_rescue", Extra::default()),
         (14, 15, N, N, Institution, "manor", Extra::default()),
         (14, 15, N, N, Sport, "free_flying", Extra::default()),
         (14, 15, N, N, Poi, "forester's_lodge", Extra::default()),
@@ -230,6 +238,9 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
         (15, 16, Y, N, Poi, "abandoned_adit", Extra { icon: Some("disused_mine"), ..Extra::default() }),
         (15, 16, Y, N, Poi, "abandoned_mineshaft", Extra { icon: Some("disused_mine"), ..Extra::default() }),
         (15, 16, N, N, Poi, "ruins", Extra::default()),
+        (15, 16, N, N, Poi, "city_gate", Extra::default()),
+        (15, 16, N, N, Poi, "border_control", Extra::default()),
+        (16, 17, N, N, Poi, "tomb", Extra::default()),
         (15, 16, N, N, Poi, "generator_wind", Extra::default()),
         (15, 16, N, N, Poi, "chimney", Extra::default()),
         (15, 16, N, N, Institution, "fire_station", Extra {
@@ -298,12 +309,16 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
         (15, 16, N, N, Poi, "water_tower", Extra::default()),
         (15, 16, N, N, Poi, "bus_stop", Extra::default()),
         (15, 16, N, N, Poi, "sauna", Extra::default()),
+        (15, 16, N, N, Poi, "spa", Extra::default()),
+        (15, 16, N, N, Water, "kneipp_water_cure", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (15, 16, N, N, Poi, "taxi", Extra::default()),
         (15, 16, N, N, Poi, "bicycle", Extra::default()),
         (15, 15, N, Y, NaturalPoi, "tree_protected", Extra { text_color: colors::TREE, ..Extra::default() }),
         (15, 15, N, Y, NaturalPoi, "tree", Extra::default()),
         (15, 16, N, N, Poi, "bird_hide", Extra::default()),
         (15, 16, N, N, Water, "dam", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
+        (15, 16, N, N, Water, "slipway", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
+        (15, 16, N, N, Water, "canoe_put_in", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (15, 16, N, N, Institution, "school", Extra { replacements: school_replacements.clone(), ..Extra::default() }),
         (15, 16, N, N, Institution, "college", Extra { replacements: college_replacements.clone(), ..Extra::default() }),
         (15, 16, N, N, Institution, "university", Extra { replacements: university_replacements.clone(), ..Extra::default() }),
@@ -313,10 +328,13 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
         }),
         (15, 16, N, N, Sport, "climbing", Extra::default()),
         (15, 16, N, N, Sport, "shooting", Extra::default()),
-        (16, 17, N, Y, NaturalPoi, "rock", Extra::default()),
-        (16, 17, N, Y, NaturalPoi, "stone", Extra::default()),
+        (16, 17, Y, Y, NaturalPoi, "rock", Extra::default()),
+        (16, 17, Y, Y, NaturalPoi, "stone", Extra::default()),
+        (15, 16, N, N, Water, "underwater_rock", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (16, 17, N, Y, NaturalPoi, "sinkhole", Extra::default()),
         (16, 17, N, N, Other, "building", Extra::default()),
+        (16, 17, N, N, Institution, "bank", Extra::default()),
+        (16, 18, N, N, Institution, "atm", Extra::default()),
         (16, 17, N, N, Water, "weir", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (16, 17, N, N, Sport, "miniature_golf", Extra::default()),
         (16, 17, N, N, Sport, "leisure_miniature_golf", Extra { icon: Some("miniature_golf"), ..Extra::default() }),
@@ -324,19 +342,30 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
         (16, 17, N, N, Sport, "tennis", Extra::default()),
         (16, 17, N, N, Sport, "basketball", Extra::default()),
         (16, 17, N, N, Sport, "volleyball", Extra::default()),
+        (16, 17, N, N, Sport, "multisport", Extra::default()),
         (16, 17, N, N, Sport, "running", Extra::default()),
         (16, 17, N, N, Sport, "athletics", Extra { icon: Some("running"), ..Extra::default() }),
         (16, 17, N, N, Sport, "swimming", Extra { icon: Some("water_park"), ..Extra::default() }),
+        (16, 17, N, N, Poi, "boat_rental", Extra::default()),
         (16, 17, N, N, Sport, "cycling", Extra::default()),
         (16, 17, N, N, Sport, "ice_skating", Extra::default()),
+        (16, 17, N, N, Poi, "bicycle_repair_station", Extra::default()),
+        (16, 17, N, N, Poi, "compressed_air", Extra::default()),
+        (16, 17, Y, N, Poi, "survey_point", Extra::default()),
         (16, NN, Y, N, Poi, "guidepost_noname", Extra { icon: Some("guidepost_x"), ..Extra::default() }),
         (16, NN, Y, N, Poi, "route_marker", Extra { icon: Some("guidepost_x"), ..Extra::default() }),
         (16, NN, N, N, Poi, "picnic_table", Extra::default()),
+        (16, 17, N, N, Poi, "shower", Extra::default()),
+        (16, 17, N, N, Poi, "sanitary_dump_station", Extra::default()),
         (16, NN, N, N, Poi, "outdoor_seating", Extra::default()),
         (16, 17, N, N, Poi, "picnic_site", Extra::default()),
         (16, 16, N, N, Poi, "board", Extra::default()),
         (16, 17, N, N, Poi, "map", Extra::default()),
         (16, 17, N, N, Poi, "artwork", Extra::default()),
+        (16, 17, N, N, Poi, "artwork_statue", Extra::default()),
+        (16, 17, N, N, Poi, "artwork_sculpture", Extra::default()),
+        (16, 17, N, N, Poi, "artwork_mural", Extra::default()),
+        (16, 17, N, N, Poi, "artwork_installation", Extra::default()),
         (16, 17, N, N, Water, "fountain", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (16, NN, N, N, Water, "watering_place", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (16, NN, N, N, Poi, "feeding_place", Extra { icon: Some("manger"), ..Extra::default() }),
@@ -357,6 +386,9 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
         (17, 18, N, N, Poi, "wayside_cross", Extra { icon: Some("cross"), ..Extra::default() }), // NOTE cross is also on lower zoom
         (17, 18, N, N, Water, "tree_shrine", Extra { icon: Some("cross"), ..Extra::default() }), // NOTE cross is also on lower zoom
         (17, NN, N, N, Poi, "firepit", Extra::default()),
+        (17, NN, N, N, Poi, "fire_hydrant", Extra::default()),
+        (17, NN, N, N, Water, "water_tank", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
+        (17, NN, N, N, Water, "suction_point", Extra { text_color: colors::WATER_LABEL, ..Extra::default() }),
         (17, NN, N, N, Poi, "toilets", Extra::default()),
         (17, NN, N, N, Poi, "bench", Extra::default()),
         (17, 18, N, N, Poi, "beehive", Extra::default()),
@@ -368,6 +400,12 @@ static POI_ENTRIES: LazyLock<Vec<PoiEntry>> = LazyLock::new(|| {
         (18, 19, N, N, Other, "building_ruins", Extra { icon: Some("ruins"), ..Extra::default() }),
         (18, 19, N, N, Poi, "post_box", Extra::default()),
         (18, 19, N, N, Poi, "telephone", Extra::default()),
+        (18, 19, N, N, Poi, "elevator", Extra::default()),
+        (18, 19, N, N, Poi, "vending_machine", Extra::default()),
+        (18, 19, N, N, Poi, "vending_machine_drinks", Extra::default()),
+        (18, 19, N, N, Poi, "vending_machine_food", Extra::default()),
+        (18, 19, N, N, Poi, "vending_machine_public_transport_tickets", Extra::default()),
+        (18, 19, N, N, Poi, "fitness_station", Extra::default()),
         (18, NN, N, N, Poi, "gate", Extra::default()),
         (18, NN, N, N, Poi, "waste_disposal", Extra::default()),
         (19, NN, N, N, Poi, "waste_basket", Extra::default()),
@@ -446,15 +484,27 @@ static OFFSETS: LazyLock<[(f64, f64); 33]> = LazyLock::new(|| {
     offsets
 });
 
+/// Type of a peak or volcano suffixed with its prominence tier by the isolation (in meters) from
+/// the `isolations` table, e.g. `peak1` for the most isolated ones.
+pub const PEAK_TIER_SQL: &str = "type || CASE
+                WHEN isolation > 4500 THEN '1'
+                WHEN isolation BETWEEN 3000 AND 4500 THEN '2'
+                WHEN isolation BETWEEN 1500 AND 3000 THEN '3'
+                ELSE ''
+            END";
+
 pub async fn query(
     ctx: &Ctx,
     client: &tokio_postgres::Client,
     kst_only: bool,
+    poi_filter: Option<&PoiFilter>,
 ) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
     let zoom = ctx.zoom;
 
     let mut selects = vec![];
 
+    let mut params = ctx.bbox_query_params(Some(1024.0));
+
     // TODO add hiking-only
     let kst_cond = if kst_only {
         r"AND (type <> 'guidepost' OR tags->'operator' ~* '\ykst\y|\ytanap\y')"
@@ -462,18 +512,19 @@ pub async fn query(
         ""
     };
 
-    selects.push(
+    let peaks_sql = format!(
         "SELECT
             osm_id,
             geometry,
             name,
-            hstore(ARRAY['ele', tags->'ele', 'isolation', tags->'isolation']) AS extra,
-            CASE WHEN type = 'volcano' THEN type
-                WHEN isolation > 4500 THEN 'peak1'
-                WHEN isolation BETWEEN 3000 AND 4500 THEN 'peak2'
-                WHEN isolation BETWEEN 1500 AND 3000 THEN 'peak3'
-                ELSE 'peak'
-            END AS type
+            hstore(ARRAY[
+                'ele', tags->'ele',
+                'isolation', tags->'isolation',
+                'linked', (tags ? 'wikidata' OR tags ? 'wikipedia')::text,
+                'image', tags->'image',
+                'wikimedia_commons', tags->'wikimedia_commons'
+            ]) AS extra,
+            {PEAK_TIER_SQL} AS type
         FROM
             osm_pois
         NATURAL LEFT JOIN
@@ -482,9 +533,11 @@ pub async fn query(
             geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5) AND
             type IN ('peak', 'volcano') AND
             name <> ''
-        ",
+        "
     );
 
+    selects.push(peaks_sql.as_str());
+
     let gte_z13_sql;
 
     if zoom >= 13 {
@@ -525,24 +578,53 @@ pub async fn query(
         );
     }
 
+    // motorway rest areas and services as landmarks on the way to trailheads
+    if zoom == 13 {
+        selects.push(
+            "SELECT
+                osm_id,
+                geometry,
+                name,
+                hstore('ele', tags->'ele') AS extra,
+                type
+            FROM
+                osm_pois
+            WHERE
+                geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5) AND
+                type IN ('rest_area', 'services')
+            ",
+        );
+    }
+
     let z14_sql;
 
     if zoom >= 14 {
-        let w = {
-            let mut omit_types = vec!["'peak'".to_string(), "'volcano'".to_string()];
+        let mut omit_types = vec!["peak".to_string(), "volcano".to_string()];
+
+        let mut sport_types = vec![];
+
+        for (typ, defs) in POIS.iter() {
+            let visible = defs
+                .iter()
+                .any(|def| def.min_zoom <= zoom && def.extra.max_zoom >= zoom)
+                && poi_filter.is_none_or(|filter| filter.allows(typ));
 
-            for (typ, defs) in POIS.iter() {
-                let visible = defs
+            if visible {
+                if defs
                     .iter()
-                    .any(|def| def.min_zoom <= zoom && def.extra.max_zoom >= zoom);
+                    .any(|def| matches!(def.category, Category::Sport))
+                {
+                    // `sport=multi` is rendered as `multisport`.
+                    let sport = if *typ == "multisport" { "multi" } else { *typ };
 
-                if !visible {
-                    omit_types.push(format!("'{typ}'"));
+                    sport_types.push(sport.to_string());
                 }
+            } else {
+                omit_types.push((*typ).to_string());
             }
+        }
 
-            format!("AND type NOT IN ({})", omit_types.join(", "))
-        };
+        params = params.push(omit_types).push(sport_types);
 
         z14_sql = format!(
             "
@@ -560,11 +642,36 @@ pub async fn query(
             hstore(ARRAY[
                 'ele', tags->'ele',
                 'access', tags->'access',
+                'locked', tags->'locked',
                 'hot', (type = 'hot_spring')::text,
                 'drinkable', tags->'drinking_water',
                 'refitted', tags->'refitted',
                 'intermittent', COALESCE(tags->'intermittent', tags->'seasonal'),
-                'water_characteristic', tags->'water_characteristic'
+                'seasonal', tags->'seasonal',
+                'operational_status', tags->'operational_status',
+                'water_characteristic', tags->'water_characteristic',
+                'fee', tags->'fee',
+                'fountain', tags->'fountain',
+                'wheelchair', tags->'wheelchair',
+                'winter_room', tags->'winter_room',
+                'shelter', tags->'shelter',
+                'mine_state', CASE
+                    WHEN type NOT IN (
+                        'adit', 'mineshaft', 'disused_adit', 'disused_mineshaft',
+                        'abandoned_adit', 'abandoned_mineshaft', 'mine_adit', 'mine_shaft'
+                    ) THEN NULL
+                    WHEN tags->'collapsed' = 'yes' OR tags->'condition' = 'collapsed'
+                    THEN 'collapsed'
+                    WHEN tags->'sealed' = 'yes' OR tags->'condition' = 'sealed'
+                    THEN 'sealed'
+                END,
+                'entrance', CASE WHEN type = 'cave_entrance' THEN tags->'entrance' END,
+                'depth', CASE WHEN type = 'cave_entrance' THEN tags->'depth' END,
+                'length', CASE WHEN type = 'cave_entrance' THEN tags->'cave:length' END,
+                'height', CASE WHEN type IN ('rock', 'stone') THEN tags->'height' END,
+                'linked', (tags ? 'wikidata' OR tags ? 'wikipedia')::text,
+                'image', tags->'image',
+                'wikimedia_commons', tags->'wikimedia_commons'
             ]) AS extra,
             CASE
                 WHEN
@@ -586,8 +693,20 @@ pub async fn query(
                     type IN ('adit', 'mineshaft') AND
                     tags->'disused' <> 'no'
                 THEN 'disused_' || type
+                WHEN
+                    type IN ('adit', 'mineshaft') AND
+                    tags->'abandoned' <> 'no'
+                THEN 'abandoned_' || type
                 WHEN type IN ('hot_spring', 'geyser', 'spring_box')
                 THEN 'spring'
+                WHEN
+                    type = 'artwork' AND
+                    tags->'artwork_type' IN ('statue', 'sculpture', 'mural', 'installation')
+                THEN 'artwork_' || (tags->'artwork_type')
+                WHEN
+                    type = 'vending_machine' AND
+                    tags->'vending' IN ('drinks', 'food', 'public_transport_tickets')
+                THEN 'vending_machine_' || (tags->'vending')
                 WHEN type IN ('tower', 'mast')
                 THEN
                     type || CASE tags->'tower:type'
@@ -617,30 +736,40 @@ pub async fn query(
                 tags->'protected' NOT IN ('', 'no') OR
                 tags->'denotation' = 'natural_monument'
             ) AND
+            (
+                type <> 'rock' OR
+                tags->'seamark:type' IS DISTINCT FROM 'rock'
+            ) AND
             (
                 type NOT IN ('saddle', 'mountain_pass') OR
                 COALESCE(NULLIF(name, ''), tags->'ref', '') <> ''
             )
-            {w} {kst_cond}
+            AND type <> ALL($6) {kst_cond}
         "
         );
 
         selects.push(&z14_sql);
 
-        // TODO filter only used sports
+        // imposm stores a row per value of `sport=a;b`; pitches with more rendered sports are
+        // collapsed to a single `multisport` icon.
         selects.push("
-            SELECT
+            SELECT DISTINCT ON (osm_id)
                 osm_id,
                 geometry,
                 name,
                 hstore(ARRAY[
                     'access', tags->'access'
                 ]) AS extra,
-                type
+                CASE
+                    WHEN type = 'multi' OR COUNT(*) OVER (PARTITION BY osm_id) > 1
+                    THEN 'multisport'
+                    ELSE type
+                END AS type
             FROM
                 osm_sports
             WHERE
                 geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5) AND
+                type = ANY($7) AND
                 osm_id NOT IN (SELECT osm_id FROM osm_pois WHERE type IN ('leisure_miniature_golf', 'leisure_horse_riding'))
         ");
 
@@ -650,7 +779,10 @@ pub async fn query(
                 osm_id,
                 geometry,
                 name,
-                hstore('') as extra,
+                hstore(ARRAY[
+                    'religion', religion,
+                    'denomination', denomination
+                ]) AS extra,
                 building AS type
             FROM
                 osm_place_of_worships
@@ -733,9 +865,7 @@ pub async fn query(
 
     drop(selects);
 
-    client
-        .query(&sql, &ctx.bbox_query_params(Some(1024.0)).as_params())
-        .await
+    client.query(&sql, &params.as_params()).await
 }
 
 pub(super) struct PendingLabel {
@@ -755,6 +885,8 @@ pub fn render_icons(
     rows: Vec<Feature>,
     collision: &mut Collision,
     svg_repo: &mut SvgRepo,
+    poi_filter: Option<&PoiFilter>,
+    mark_linked: bool,
 ) -> Result<ToLabel, LayerRenderError> {
     let _span = tracy_client::span!("pois::render_icons");
 
@@ -765,6 +897,10 @@ pub fn render_icons(
     for row in rows {
         let typ = row.get_string("type")?;
 
+        if poi_filter.is_some_and(|filter| !filter.allows(typ)) {
+            continue;
+        }
+
         let extra = row.get_hstore("extra")?;
 
         let Some(def) = POIS.get(typ).and_then(|defs| {
@@ -776,7 +912,11 @@ pub fn render_icons(
 
         let point = row.get_point()?.project_to_tile(&ctx.tile_projector);
 
-        let key = def.extra.icon.unwrap_or(typ);
+        let key = worship_icon(
+            def.extra.icon.unwrap_or(typ),
+            extra.get("religion").and_then(Option::as_deref),
+            extra.get("denomination").and_then(Option::as_deref),
+        );
 
         let (key, names, stylesheet) = match key {
             "spring" => {
@@ -841,6 +981,111 @@ pub fn render_icons(
 
                 (Cow::Owned(key), names, Some(stylesheet))
             }
+            "drinking_water"
+                if extra
+                    .get("operational_status")
+                    .and_then(Option::as_deref)
+                    .is_some_and(|status| {
+                        matches!(status, "broken" | "out_of_order" | "closed")
+                    }) =>
+            {
+                (
+                    Cow::Borrowed("drinking_water|broken"),
+                    vec![key.to_string(), "broken_drinking_water".to_string()],
+                    None,
+                )
+            }
+            "drinking_water"
+                if extra
+                    .get("fountain")
+                    .and_then(Option::as_deref)
+                    .is_some_and(|fountain| !matches!(fountain, "" | "no")) =>
+            {
+                (
+                    Cow::Borrowed("drinking_fountain"),
+                    vec!["drinking_fountain".to_string()],
+                    None,
+                )
+            }
+            // decorative fountains are told apart from drinkable ones by the spring marker
+            "fountain" => match extra.get("drinkable").and_then(Option::as_deref) {
+                Some("yes" | "treated") => (
+                    Cow::Borrowed("fountain|drinkable"),
+                    vec![key.to_string(), "drinkable_spring".to_string()],
+                    Some(r"#drinkable { fill: #00ff00 }".to_string()),
+                ),
+                Some("no") => (
+                    Cow::Borrowed("fountain|not_drinkable"),
+                    vec![key.to_string(), "drinkable_spring".to_string()],
+                    Some(r"#drinkable { fill: #ff0000 }".to_string()),
+                ),
+                _ => (Cow::Borrowed(key), vec![key.to_string()], None),
+            },
+            "toilets" => {
+                let mut key = key.to_string();
+                let mut names = vec![key.clone()];
+
+                if extra
+                    .get("fee")
+                    .is_some_and(|fee| fee.as_deref().is_some_and(|fee| !matches!(fee, "" | "no")))
+                {
+                    key.push_str("|fee");
+                    names.push("fee_badge".into());
+                }
+
+                if extra.get("wheelchair").is_some_and(|wheelchair| {
+                    matches!(wheelchair.as_deref(), Some("yes" | "designated"))
+                }) {
+                    key.push_str("|wheelchair");
+                    names.push("wheelchair_badge".into());
+                }
+
+                (Cow::Owned(key), names, None)
+            }
+            "alpine_hut"
+                if extra
+                    .get("winter_room")
+                    .is_some_and(|winter_room| winter_room.as_deref() == Some("yes")) =>
+            {
+                (
+                    Cow::Owned(format!("{key}|winter_room")),
+                    vec![key.to_string(), "winter_room_badge".to_string()],
+                    None,
+                )
+            }
+            "hunting_stand"
+                if extra
+                    .get("shelter")
+                    .is_some_and(|shelter| shelter.as_deref() == Some("yes")) =>
+            {
+                (
+                    Cow::Owned(format!("{key}|enclosed")),
+                    vec![key.to_string(), "enclosed_badge".to_string()],
+                    None,
+                )
+            }
+            "mine" | "disused_mine"
+                if extra.get("mine_state").is_some_and(|state| {
+                    matches!(state.as_deref(), Some("collapsed" | "sealed"))
+                }) =>
+            {
+                (
+                    Cow::Owned(format!("{key}|closed")),
+                    vec![key.to_string(), "slash_badge".to_string()],
+                    None,
+                )
+            }
+            "gate" | "lift_gate"
+                if extra
+                    .get("locked")
+                    .is_some_and(|locked| locked.as_deref() == Some("yes")) =>
+            {
+                (
+                    Cow::Owned(format!("{key}|locked")),
+                    vec!["padlock".to_string(), key.to_string()],
+                    None,
+                )
+            }
             _ => (
                 Cow::Borrowed(key),
                 vec![key.to_string()],
@@ -848,6 +1093,19 @@ pub fn render_icons(
             ),
         };
 
+        let name = row.get_string("name")?;
+
+        let is_boulder = matches!(typ, "rock" | "stone");
+
+        let scale = if is_boulder {
+            boulder_scale(
+                parse_meters(extra.get("height").and_then(Option::as_deref)),
+                !name.is_empty(),
+            )
+        } else {
+            1.0
+        };
+
         let surface = svg_repo.get_extra(
             &key,
             Some({
@@ -862,6 +1120,8 @@ pub fn render_icons(
 
         let (x, y, w, he) = surface.ink_extents();
 
+        let (w, he) = (w * scale, he * scale);
+
         let corner_x = point.x() - w / 2.0;
 
         let corner_y = point.y() - he / 2.0;
@@ -878,9 +1138,8 @@ pub fn render_icons(
 
             let bbox_idx = collision.add(bbox);
 
-            if def.min_text_zoom <= zoom {
-                let name = row.get_string("name")?;
-
+            // notable boulders are labelled as soon as they are shown
+            if def.min_text_zoom <= zoom || scale > 1.0 {
                 if !name.is_empty() {
                     let name = replace(name, &def.extra.replacements);
 
@@ -888,7 +1147,18 @@ pub fn render_icons(
                         point: Point::new(point.x() + dx, point.y() + dy),
                         icon_half_height: he / 2.0,
                         name: name.into_owned(),
-                        ele: extra.get("ele").and_then(Option::clone),
+                        ele: if typ == "cave_entrance" {
+                            cave_sub_label(
+                                extra.get("ele").and_then(Option::as_deref),
+                                extra.get("depth").and_then(Option::as_deref),
+                                extra.get("length").and_then(Option::as_deref),
+                            )
+                        } else if is_boulder {
+                            parse_meters(extra.get("height").and_then(Option::as_deref))
+                                .map(|height| format!("↑{height} m"))
+                        } else {
+                            extra.get("ele").and_then(Option::clone)
+                        },
                         bbox_idx,
                         def,
                     });
@@ -897,7 +1167,10 @@ pub fn render_icons(
 
             let _span = tracy_client::span!("features::paint_svg");
 
-            context.set_source_surface(surface, corner_x - x, corner_y - y)?;
+            context.save()?;
+            context.translate(corner_x, corner_y);
+            context.scale(scale, scale);
+            context.set_source_surface(surface, -x, -y)?;
 
             context.paint_with_alpha(
                 if typ != "cave_entrance"
@@ -906,11 +1179,43 @@ pub fn render_icons(
                         .is_some_and(|access| matches!(access.as_deref(), Some("private" | "no")))
                 {
                     0.33
+                } else if typ == "cave_entrance"
+                    && extra.get("entrance").is_some_and(|entrance| {
+                        matches!(entrance.as_deref(), Some("secondary" | "emergency"))
+                    })
+                {
+                    0.66
+                } else if matches!(
+                    def.extra.icon.unwrap_or(typ),
+                    "drinking_water" | "water_point"
+                ) && extra
+                    .get("seasonal")
+                    .and_then(Option::as_deref)
+                    .is_some_and(|seasonal| !matches!(seasonal, "" | "no"))
+                {
+                    0.5
                 } else {
                     1.0
                 },
             )?;
 
+            context.restore()?;
+
+            // debug overlay visualizing `wikidata`/`wikipedia` linkage
+            if mark_linked
+                && extra
+                    .get("linked")
+                    .is_some_and(|linked| linked.as_deref() == Some("true"))
+            {
+                context.new_path();
+                context.arc(corner_x + w, corner_y, 2.5, 0.0, 2.0 * f64::consts::PI);
+                context.set_source_color(colors::WHITE);
+                context.set_line_width(1.0);
+                context.stroke_preserve()?;
+                context.set_source_color(colors::WIKIDATA_LINKED);
+                context.fill()?;
+            }
+
             break 'outer;
         }
     }
@@ -918,6 +1223,72 @@ pub fn render_icons(
     Ok(to_label)
 }
 
+/// Elevation of a cave entrance followed by the depth and length of significant caves.
+fn cave_sub_label(ele: Option<&str>, depth: Option<&str>, length: Option<&str>) -> Option<String> {
+    const MIN_DEPTH: f64 = 50.0;
+    const MIN_LENGTH: f64 = 500.0;
+
+    let meters = |value: Option<&str>, min: f64| parse_meters(value).filter(|value| *value >= min);
+
+    let parts: Vec<_> = [
+        ele.filter(|ele| !ele.is_empty()).map(str::to_string),
+        meters(depth, MIN_DEPTH).map(|depth| format!("↓{depth} m")),
+        meters(length, MIN_LENGTH).map(|length| format!("↔{length} m")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Length in meters of a tag value like `12`, `12m` or `12 m`.
+fn parse_meters(value: Option<&str>) -> Option<f64> {
+    value.and_then(|value| {
+        value
+            .trim()
+            .trim_end_matches('m')
+            .trim()
+            .parse::<f64>()
+            .ok()
+    })
+}
+
+/// Icon scale of a rock or stone; named ones are enlarged slightly and the tall ones more.
+fn boulder_scale(height: Option<f64>, named: bool) -> f64 {
+    match height {
+        Some(height) if height > 0.0 => (1.2 + height / 50.0).min(1.5),
+        _ if named => 1.2,
+        _ => 1.0,
+    }
+}
+
+/// Icon of a place of worship according to its religion, e.g. a mosque in a building tagged as
+/// a church, or the orthodox cross for the eastern churches.
+fn worship_icon<'a>(key: &'a str, religion: Option<&str>, denomination: Option<&str>) -> &'a str {
+    if !matches!(key, "church" | "chapel" | "synagogue" | "mosque") {
+        return key;
+    }
+
+    match religion {
+        Some("muslim") => "mosque",
+        Some("jewish") => "synagogue",
+        Some("christian")
+            if denomination.is_some_and(|denomination| {
+                denomination.ends_with("orthodox")
+                    || matches!(denomination, "greek_catholic" | "old_believers")
+            }) =>
+        {
+            if key == "chapel" {
+                "chapel_orthodox"
+            } else {
+                "church_orthodox"
+            }
+        }
+        _ => key,
+    }
+}
+
 pub fn render_labels(
     _ctx: &Ctx,
     context: &Context,
@@ -978,3 +1349,47 @@ pub fn render_labels(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cave_sub_label_shows_significant_dimensions() {
+        assert_eq!(
+            cave_sub_label(Some("712"), Some("120 m"), Some("300")),
+            Some("712 ↓120 m".into())
+        );
+        assert_eq!(
+            cave_sub_label(None, Some("12"), Some("2300")),
+            Some("↔2300 m".into())
+        );
+        assert_eq!(cave_sub_label(Some(""), Some("deep"), None), None);
+    }
+
+    #[test]
+    fn boulder_scale_grows_with_height() {
+        assert!((boulder_scale(None, false) - 1.0).abs() < f64::EPSILON);
+        assert!((boulder_scale(None, true) - 1.2).abs() < f64::EPSILON);
+        assert!((boulder_scale(parse_meters(Some("5 m")), false) - 1.3).abs() < 1e-9);
+        assert!((boulder_scale(parse_meters(Some("40")), false) - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn worship_icon_follows_religion() {
+        assert_eq!(
+            worship_icon("church", Some("christian"), Some("roman_catholic")),
+            "church"
+        );
+        assert_eq!(
+            worship_icon("chapel", Some("christian"), Some("greek_catholic")),
+            "chapel_orthodox"
+        );
+        assert_eq!(
+            worship_icon("church", Some("christian"), Some("russian_orthodox")),
+            "church_orthodox"
+        );
+        assert_eq!(worship_icon("church", Some("muslim"), None), "mosque");
+        assert_eq!(worship_icon("fountain", Some("jewish"), None), "fountain");
+    }
+}
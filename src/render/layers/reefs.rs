@@ -0,0 +1,52 @@
+use crate::render::{
+    Feature, ctx::Ctx, draw::path_geom::path_geometry, layer_render_error::LayerRenderResult,
+    layers::landcover::tile_pattern, projectable::TileProjectable, svg_repo::SvgRepo,
+};
+use cairo::Context;
+
+pub async fn query(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    let query = "
+        SELECT
+            geometry
+        FROM
+            osm_landcovers
+        WHERE
+            geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5) AND
+            type = 'reef'
+    ";
+
+    client
+        .query(query, &ctx.bbox_query_params(Some(4.0)).as_params())
+        .await
+}
+
+pub fn render(
+    ctx: &Ctx,
+    context: &Context,
+    rows: Vec<Feature>,
+    svg_repo: &mut SvgRepo,
+) -> LayerRenderResult {
+    let _span = tracy_client::span!("reefs::render");
+
+    context.save()?;
+
+    let pattern = tile_pattern(ctx, svg_repo.get("reef")?);
+
+    context.set_source(&pattern)?;
+
+    for row in rows {
+        path_geometry(
+            context,
+            &row.get_geometry()?.project_to_tile(&ctx.tile_projector),
+        );
+
+        context.fill()?;
+    }
+
+    context.restore()?;
+
+    Ok(())
+}
@@ -8,7 +8,8 @@ use crate::render::{
     svg_repo::SvgRepo,
 };
 use cairo::Context;
-use std::borrow::Cow;
+use geo::Coord;
+use std::{borrow::Cow, collections::HashSet};
 
 pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
     let zoom = ctx.zoom;
@@ -43,6 +44,7 @@ pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tok
     #[cfg_attr(any(), rustfmt::skip)]
     let query = format!("
         SELECT
+            {table}.osm_id,
             {table}.geometry,
             {table}.type,
             tracktype,
@@ -53,7 +55,14 @@ pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tok
             oneway,
             bicycle,
             foot,
-            trail_visibility
+            trail_visibility,
+            sac_scale,
+            COALESCE({table}.tags->'conveying', '') AS conveying,
+            COALESCE({table}.tags->'piste:grooming', '') AS piste_grooming,
+            COALESCE({table}.tags->'layer', '') AS layer,
+            COALESCE({table}.tags->'surface', '') AS surface,
+            COALESCE({table}.tags->'smoothness', '') AS smoothness,
+            COALESCE({table}.tags->'construction', '') AS construction
             {select_member}
         FROM
             {table}
@@ -113,15 +122,23 @@ pub fn render(
 
     context.save()?;
 
-    let rows: Vec<_> = rows
-        .iter()
-        .map(|row| {
-            Ok((
-                row,
-                row.get_line_string()?.project_to_tile(&ctx.tile_projector),
-            ))
-        })
-        .collect::<Result<Vec<_>, FeatureError>>()?;
+    let rows = in_paint_order(
+        rows.iter()
+            .map(|row| {
+                Ok((
+                    road_level(
+                        row.get_string("layer")?,
+                        row.get_i16("bridge")? > 0,
+                        row.get_i16("tunnel")? > 0,
+                    ),
+                    (
+                        row,
+                        row.get_line_string()?.project_to_tile(&ctx.tile_projector),
+                    ),
+                ))
+            })
+            .collect::<Result<Vec<_>, FeatureError>>()?,
+    );
 
     for (row, geom) in &rows {
         let typ = row.get_string("type")?;
@@ -165,11 +182,20 @@ pub fn render(
             (12.., "highway", "path") if bicycle != "designated" && (zoom > 12 || is_in_route) => {
                 apply_glow_defaults_a(1.0, trail_visibility);
                 draw()?;
+
+                if zoom >= 13
+                    && let Some((color, dash)) = sac_scale_casing(row.get_i32("sac_scale")?)
+                {
+                    context.set_source_color_a(color, 0.6 * trail_visibility);
+                    context.set_line_width(ke() + 2.5);
+                    context.set_dash(dash, 0.0);
+                    draw()?;
+                }
             }
             (12.., "highway", _)
                 if typ == "track" && (zoom > 12 || is_in_route || tracktype == "grade1")
                     || typ == "service" && service != "parking_aisle"
-                    || ["escape", "corridor", "bus_guideway"].contains(&typ) =>
+                    || ["escape", "bus_guideway"].contains(&typ) =>
             {
                 apply_glow_defaults_a(ke() * 1.2, trail_visibility);
                 draw()?;
@@ -180,7 +206,13 @@ pub fn render(
             }
             (13.., "highway", "bridleway") => {
                 apply_glow_defaults(1.2);
-                context.set_source_color_a(colors::BRIDLEWAY2, trail_visibility);
+                context.set_source_color_a(
+                    bridleway_surface_color(
+                        row.get_string("surface")?,
+                        row.get_string("smoothness")?,
+                    ),
+                    trail_visibility,
+                );
                 draw()?;
             }
             (_, "highway", "motorway" | "trunk") => {
@@ -191,6 +223,10 @@ pub fn render(
                 apply_highway_defaults(3.666);
                 draw()?;
             }
+            (_, "highway", "construction") => {
+                apply_highway_defaults(construction_width(row.get_string("construction")?) + 1.5);
+                draw()?;
+            }
             (_, "highway", "primary_link" | "secondary") | (_, _, "construction") => {
                 apply_highway_defaults(3.333);
                 draw()?;
@@ -213,6 +249,19 @@ pub fn render(
         }
     }
 
+    // Tunnel ends touching a surface road are marked as portals.
+    let mut surface_ends = HashSet::new();
+
+    if zoom >= 14 {
+        for (row, geom) in &rows {
+            if row.get_i16("tunnel")? == 0 {
+                for end in [geom.0.first(), geom.0.last()].into_iter().flatten() {
+                    surface_ends.insert(coord_key(end));
+                }
+            }
+        }
+    }
+
     for (row, geom) in &rows {
         let typ = row.get_string("type")?;
         let class = row.get_string("class")?;
@@ -286,6 +335,17 @@ pub fn render(
                 context.paint()?;
 
                 context.restore()?;
+
+                let coords = &geom.0;
+                let n = coords.len();
+
+                if n >= 2 {
+                    for (end, inner) in [(coords[0], coords[1]), (coords[n - 1], coords[n - 2])] {
+                        if surface_ends.contains(&coord_key(&end)) {
+                            draw_tunnel_portal(context, end, inner, width + 2.0)?;
+                        }
+                    }
+                }
             }
 
             Ok(())
@@ -344,7 +404,24 @@ pub fn render(
             false
         };
 
-        let trail_visibility = 0.666f64.powf(row.get_i32("trail_visibility")? as f64);
+        let visibility = row.get_i32("trail_visibility")?;
+
+        let trail_visibility = 0.666f64.powf(visibility as f64);
+
+        let conveying = row.get_string("conveying")?;
+
+        if zoom >= 16
+            && class == "highway"
+            && matches!(typ, "footway" | "steps")
+            && !matches!(conveying, "" | "no")
+        {
+            context.save()?;
+            apply_highway_defaults(if typ == "steps" { 6.0 } else { 4.5 });
+            context.set_line_cap(cairo::LineCap::Butt);
+            context.set_source_color_a(colors::CONVEYING, 0.5);
+            draw()?;
+            context.restore()?;
+        }
 
         match (zoom, class, typ) {
             (14.., _, "pier") => {
@@ -439,8 +516,19 @@ pub fn render(
                 draw_bridges_tunnels(1.5 + 1.0 / 3.0 + 1.0)?;
             }
             (12.., "highway", "construction") => {
-                apply_highway_defaults(1.5 + 1.0 / 3.0);
-                context.set_source_color(colors::CONSTRUCTION_ROAD_1);
+                let construction = row.get_string("construction")?;
+
+                apply_highway_defaults(construction_width(construction));
+                context.set_source_color(
+                    if matches!(
+                        construction,
+                        "motorway" | "trunk" | "motorway_link" | "trunk_link"
+                    ) {
+                        colors::SUPERROAD
+                    } else {
+                        colors::CONSTRUCTION_ROAD_1
+                    },
+                );
                 context.set_dash(&[5.0, 5.0], 0.0);
                 draw()?;
 
@@ -495,6 +583,19 @@ pub fn render(
 
                 draw_bridges_tunnels(1.2 + 1.0)?;
             }
+            (13.., "piste:type", "nordic") => {
+                let (width, dash): (f64, &[f64]) = match row.get_string("piste_grooming")? {
+                    "backcountry" | "no" => (1.0, &[1.0, 2.0]),
+                    "skating" | "classic+skating" | "classic;skating" => (1.5, &[5.0, 2.0]),
+                    _ => (1.0, &[5.0, 2.0]),
+                };
+
+                apply_highway_defaults(width);
+                context.set_line_cap(cairo::LineCap::Butt);
+                context.set_dash(dash, 0.0);
+                context.set_source_color(colors::PISTE_NORDIC);
+                draw()?;
+            }
             (14.., "highway", "footway" | "pedestrian")
             | (14.., "highway" | "railway" | "public_transport", "platform") => {
                 apply_highway_defaults(1.0);
@@ -508,9 +609,16 @@ pub fn render(
                 context.set_dash(&[1.0, 2.0], 2.0);
                 draw()?;
             }
+            // indoor connectors must not clutter the outdoor map
+            (19.., "highway", "corridor") => {
+                apply_highway_defaults(1.0);
+                context.set_dash(&[2.0, 2.0], 0.0);
+                context.set_source_color_a(colors::TRACK, 0.4);
+                draw()?;
+            }
             (12.., "highway", _)
                 if typ == "service" && service != "parking_aisle"
-                    || ["escape", "corridor", "bus_guideway"].contains(&typ) =>
+                    || ["escape", "bus_guideway"].contains(&typ) =>
             {
                 let width = ke() * 1.2;
 
@@ -554,9 +662,20 @@ pub fn render(
                 let width = ke();
 
                 apply_highway_defaults(width);
-                context.set_dash(&[3.0, 3.0], 0.0);
-                context.set_source_color_a(colors::TRACK, trail_visibility);
-                draw()?;
+
+                if visibility == TRAIL_VISIBILITY_NO {
+                    // a faint ghost of a mapped path which can't be followed on the ground
+                    context.save()?;
+                    context.set_line_cap(cairo::LineCap::Round);
+                    context.set_dash(&[0.0, 3.0], 0.0);
+                    context.set_source_color_a(colors::TRACK, 0.4);
+                    draw()?;
+                    context.restore()?;
+                } else {
+                    context.set_dash(&[3.0, 3.0], 0.0);
+                    context.set_source_color_a(colors::TRACK, trail_visibility);
+                    draw()?;
+                }
 
                 draw_bridges_tunnels(width + 1.0)?;
             }
@@ -606,7 +725,12 @@ pub fn render(
             _ => (),
         }
 
-        let oneway = row.get_i16("oneway")?;
+        // moving walkways and escalators show their running direction like oneways
+        let oneway = match conveying {
+            "forward" if zoom >= 16 => 1,
+            "backward" if zoom >= 16 => -1,
+            _ => row.get_i16("oneway")?,
+        };
 
         if zoom >= 14 && oneway != 0 {
             path_line_string(context, geom);
@@ -632,3 +756,192 @@ pub fn render(
 
     Ok(())
 }
+
+/// Enumerated `trail_visibility=no`, the highest of the values.
+const TRAIL_VISIBILITY_NO: i32 = 6;
+
+fn coord_key(coord: &Coord) -> (u64, u64) {
+    (coord.x.to_bits(), coord.y.to_bits())
+}
+
+/// Draws a portal bracket across the line end `end` with wings flaring away from the tunnel.
+fn draw_tunnel_portal(
+    context: &Context,
+    end: Coord,
+    inner: Coord,
+    width: f64,
+) -> cairo::Result<()> {
+    let dx = end.x - inner.x;
+    let dy = end.y - inner.y;
+
+    let len = dx.hypot(dy);
+
+    if len < f64::EPSILON {
+        return Ok(());
+    }
+
+    let (dx, dy) = (dx / len, dy / len);
+    let (nx, ny) = (-dy, dx);
+
+    let half = width.mul_add(0.5, 0.5);
+
+    context.save()?;
+
+    context.set_dash(&[], 0.0);
+    context.set_source_rgb(0.0, 0.0, 0.0);
+    context.set_line_width(1.0);
+    context.set_line_cap(cairo::LineCap::Butt);
+    context.set_line_join(cairo::LineJoin::Miter);
+
+    context.move_to(
+        dx.mul_add(2.0, nx.mul_add(half + 1.5, end.x)),
+        dy.mul_add(2.0, ny.mul_add(half + 1.5, end.y)),
+    );
+    context.line_to(nx.mul_add(half, end.x), ny.mul_add(half, end.y));
+    context.line_to(nx.mul_add(-half, end.x), ny.mul_add(-half, end.y));
+    context.line_to(
+        dx.mul_add(2.0, nx.mul_add(-half - 1.5, end.x)),
+        dy.mul_add(2.0, ny.mul_add(-half - 1.5, end.y)),
+    );
+
+    context.stroke()?;
+
+    context.restore()
+}
+
+/// Casing color and dash of a path by its `sac_scale`, enumerated from 1 for `hiking` (T1) to 6
+/// for `difficult_alpine_hiking` (T6). Plain hiking trails get no casing.
+const fn sac_scale_casing(sac_scale: i32) -> Option<(Color, &'static [f64])> {
+    match sac_scale {
+        2 => Some((colors::SAC_SCALE_MOUNTAIN, &[])),
+        3 => Some((colors::SAC_SCALE_DEMANDING, &[])),
+        4 => Some((colors::SAC_SCALE_ALPINE, &[])),
+        5 => Some((colors::SAC_SCALE_ALPINE, &[6.0, 2.0])),
+        6 => Some((colors::SAC_SCALE_ALPINE, &[2.0, 2.0])),
+        _ => None,
+    }
+}
+
+/// Casing of a bridleway by its suitability for horses: sealed surfaces are hard on hooves,
+/// rough or muddy ones are hard to ride.
+fn bridleway_surface_color(surface: &str, smoothness: &str) -> Color {
+    if matches!(
+        smoothness,
+        "very_bad" | "horrible" | "very_horrible" | "impassable"
+    ) || matches!(surface, "rock" | "mud" | "scree" | "stepping_stones")
+    {
+        colors::BRIDLEWAY_ROUGH
+    } else if matches!(
+        surface,
+        "paved"
+            | "asphalt"
+            | "chipseal"
+            | "concrete"
+            | "concrete:plates"
+            | "concrete:lanes"
+            | "paving_stones"
+            | "sett"
+            | "unhewn_cobblestone"
+            | "cobblestone"
+            | "metal"
+            | "wood"
+    ) {
+        colors::BRIDLEWAY_HARD
+    } else {
+        colors::BRIDLEWAY2
+    }
+}
+
+/// Width of a road under construction hinting the class it is being built as. Untagged
+/// construction keeps the width of a secondary road.
+fn construction_width(construction: &str) -> f64 {
+    match construction {
+        "motorway" | "trunk" => 2.5,
+        "primary" | "motorway_link" | "trunk_link" => 1.5 + 2.0 / 3.0,
+        "secondary_link" | "tertiary" | "tertiary_link" => 1.5,
+        "living_street" | "residential" | "unclassified" | "road" | "service" => 1.0,
+        _ => 1.5 + 1.0 / 3.0,
+    }
+}
+
+/// Vertical level of a road. An explicit `layer` wins, otherwise bridges are one level above
+/// and tunnels one level below the ground.
+fn road_level(layer: &str, bridge: bool, tunnel: bool) -> i32 {
+    layer.trim().parse().unwrap_or(if bridge {
+        1
+    } else if tunnel {
+        -1
+    } else {
+        0
+    })
+}
+
+/// Roads of the query as they are painted. Bridges must be painted over the roads they cross
+/// and those over tunnels, so the level takes precedence over the z-order of the query; the sort
+/// is stable to keep the latter within a level.
+fn in_paint_order<T>(mut roads: Vec<(i32, T)>) -> Vec<T> {
+    roads.sort_by_key(|(level, _)| *level);
+
+    roads.into_iter().map(|(_, road)| road).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_falls_back_to_bridge_and_tunnel() {
+        assert_eq!(road_level("", true, false), 1);
+        assert_eq!(road_level("", false, true), -1);
+        assert_eq!(road_level("", false, false), 0);
+        assert_eq!(road_level("2", true, false), 2);
+        assert_eq!(road_level("-1", false, false), -1);
+        assert_eq!(road_level("x", true, false), 1);
+    }
+
+    #[test]
+    fn only_mountain_paths_get_a_casing() {
+        assert!(sac_scale_casing(0).is_none());
+        assert!(sac_scale_casing(1).is_none());
+        assert!(sac_scale_casing(2).is_some_and(|(_, dash)| dash.is_empty()));
+        assert!(sac_scale_casing(6).is_some_and(|(_, dash)| !dash.is_empty()));
+    }
+
+    #[test]
+    fn bridleway_surface_suitability() {
+        assert_eq!(bridleway_surface_color("", ""), colors::BRIDLEWAY2);
+        assert_eq!(bridleway_surface_color("grass", "good"), colors::BRIDLEWAY2);
+        assert_eq!(
+            bridleway_surface_color("asphalt", ""),
+            colors::BRIDLEWAY_HARD
+        );
+        assert_eq!(bridleway_surface_color("mud", ""), colors::BRIDLEWAY_ROUGH);
+        assert_eq!(
+            bridleway_surface_color("asphalt", "horrible"),
+            colors::BRIDLEWAY_ROUGH
+        );
+    }
+
+    #[test]
+    fn construction_width_follows_future_class() {
+        assert!(construction_width("motorway") > construction_width("secondary"));
+        assert!(construction_width("residential") < construction_width("tertiary"));
+        assert_eq!(construction_width(""), construction_width("secondary"));
+    }
+
+    #[test]
+    fn bridge_over_road_over_tunnel() {
+        // as returned by the query, ordered by z-order (motorway ranks highest)
+        let scene = vec![
+            (road_level("", false, true), "motorway tunnel"),
+            (road_level("", false, false), "motorway"),
+            (road_level("", true, false), "track bridge"),
+            (road_level("", false, false), "primary"),
+        ];
+
+        assert_eq!(
+            in_paint_order(scene),
+            ["motorway tunnel", "motorway", "primary", "track bridge"]
+        );
+    }
+}
@@ -1,12 +1,15 @@
 use crate::render::{
     Feature, RenderLayer,
     collision::Collision,
+    colors::{self, ContextExt},
     ctx::Ctx,
     draw::{
         font_options::FontAndLayoutOptions,
         line_pattern::draw_line_pattern_scaled,
+        markers_on_path::draw_markers_on_path,
         offset_line::offset_line_string,
-        path_geom::{path_line_string_with_offset, walk_geometry_line_strings},
+        path_geom::{path_line_string, path_line_string_with_offset, walk_geometry_line_strings},
+        text::{TextOptions, draw_text},
         text_on_line::{Align, Distribution, Repeat, TextOnLineOptions, draw_text_on_line},
     },
     layer_render_error::{LayerRenderError, LayerRenderResult},
@@ -15,7 +18,9 @@ use crate::render::{
 };
 use cairo::Context;
 use colorsys::{Rgb, RgbRatio};
-use std::collections::HashSet;
+use cosmic_text::Weight;
+use geo::{Coord, Euclidean, Length, Rect};
+use std::{cell::RefCell, collections::HashSet};
 
 const COLOR_SQL: &str = r#"
   CASE
@@ -53,6 +58,10 @@ const COLORS: [(&str, &str); 9] = [
     ("red", "ff3030"),
 ];
 
+const SHIELD_FONT_SIZE: f64 = 10.0;
+
+const SHIELD_SPACING: f64 = 300.0;
+
 fn format_vec(vec: &[&str]) -> String {
     if vec.is_empty() {
         "'_x_'".to_string()
@@ -178,6 +187,7 @@ fn get_routes_query(
             idx(arr1, 48) AS r_none,
             refs1,
             refs2,
+            shields,
             icount(arr1 - array[1000, 1010, 1020, 1030, 1040]) AS off1,
             icount(arr2 - array[1000, 1010, 1020, 1030, 1040]) AS off2
         FROM (
@@ -223,6 +233,25 @@ fn get_routes_query(
                     ),
                     ', '
                 ) AS refs2,
+                array_to_string(
+                    array(
+                        SELECT distinct itm FROM unnest(
+                            array_agg(
+                                CASE
+                                WHEN
+                                    osm_routes.type IN ({lefts_in}, {rights_in}) AND
+                                    network IN ('iwn', 'nwn', 'icn', 'ncn') AND
+                                    ref <> ''
+                                THEN
+                                    ({COLOR_SQL}) || ':' || ref
+                                ELSE
+                                    null
+                                END
+                            )
+                        ) AS itm ORDER BY itm
+                    ),
+                    ';'
+                ) AS shields,
                 first(geometry) AS geometry,
                 uniq(sort(array_agg(
                     CASE
@@ -271,7 +300,7 @@ fn get_routes_query(
             b_red, b_blue, b_green, b_yellow, b_black, b_white, b_orange, b_purple, b_none,
             s_red, s_blue, s_green, s_yellow, s_black, s_white, s_orange, s_purple, s_none,
             r_red, r_blue, r_green, r_yellow, r_black, r_white, r_orange, r_purple, r_none,
-            off1, off2, refs1, refs2
+            off1, off2, refs1, refs2, shields
     ")
 }
 
@@ -503,7 +532,37 @@ pub fn render_labels(
         let refs2 = row.get_string("refs2")?;
         let off2 = row.get_i32("off2")?;
 
+        let shields = parse_shields(row.get_string("shields")?);
+
         walk_geometry_line_strings(&geom, &mut |geom| {
+            if !shields.is_empty() {
+                path_line_string(context, geom);
+
+                let path = context.copy_path_flat()?;
+
+                context.new_path();
+
+                let positions = RefCell::new(Vec::new());
+
+                // first shield is at the half of the spacing or of a shorter line
+                let first = Euclidean.length(geom).min(SHIELD_SPACING) / 2.0;
+
+                draw_markers_on_path(
+                    &path,
+                    SHIELD_SPACING - first,
+                    SHIELD_SPACING,
+                    &|x, y, _| -> cairo::Result<()> {
+                        positions.borrow_mut().push((x, y));
+
+                        Ok(())
+                    },
+                )?;
+
+                for (x, y) in positions.into_inner() {
+                    draw_shields(context, collision, x, y, &shields)?;
+                }
+            }
+
             let mut options = TextOnLineOptions {
                 flo: FontAndLayoutOptions {
                     size: 11.0,
@@ -533,3 +592,126 @@ pub fn render_labels(
 
     Ok(())
 }
+
+/// Parses `color_index:ref` items of the `shields` column into the route color name, its hex
+/// value and the ref.
+fn parse_shields(shields: &str) -> Vec<(&'static str, &'static str, &str)> {
+    shields
+        .split(';')
+        .filter_map(|item| {
+            let (index, r#ref) = item.split_once(':')?;
+
+            let index = index.parse::<usize>().ok()?.min(COLORS.len() - 1);
+
+            // `COLORS` are ordered reversely to the `COLOR_SQL` indices
+            let (name, hex) = COLORS[COLORS.len() - 1 - index];
+
+            Some((name, hex, r#ref))
+        })
+        .collect()
+}
+
+/// Draws the refs as boxes filled with the route color, side by side centered at the point.
+/// Nothing is drawn if the group collides.
+fn draw_shields(
+    context: &Context,
+    collision: &mut Collision,
+    x: f64,
+    y: f64,
+    shields: &[(&str, &str, &str)],
+) -> cairo::Result<()> {
+    const PADDING: f64 = 3.0;
+    const GAP: f64 = 2.0;
+    // glyph advances are estimated as the text is not shaped yet
+    const GLYPH_WIDTH_EMS: f64 = 0.6;
+
+    let widths: Vec<f64> = shields
+        .iter()
+        .map(|(_, _, r#ref)| {
+            (r#ref.chars().count() as f64 * SHIELD_FONT_SIZE)
+                .mul_add(GLYPH_WIDTH_EMS, PADDING * 2.0)
+        })
+        .collect();
+
+    let width = widths.iter().sum::<f64>() + GAP * (widths.len() - 1) as f64;
+    let height = SHIELD_FONT_SIZE + PADDING * 2.0;
+
+    let rect = Rect::new(
+        Coord {
+            x: x - width / 2.0,
+            y: y - height / 2.0,
+        },
+        Coord {
+            x: x + width / 2.0,
+            y: y + height / 2.0,
+        },
+    );
+
+    if collision.collides(&rect) {
+        return Ok(());
+    }
+
+    let _ = collision.add(rect);
+
+    let mut left = rect.min().x;
+
+    for (&(name, hex, r#ref), w) in shields.iter().zip(widths) {
+        let rgb: RgbRatio = Rgb::from_hex_str(hex).expect("color").as_ratio();
+
+        context.rectangle(left, rect.min().y, w, height);
+        context.set_source_rgb(rgb.r(), rgb.g(), rgb.b());
+        context.fill_preserve()?;
+        context.set_source_color(colors::WHITE);
+        context.set_line_width(1.0);
+        context.set_dash(&[], 0.0);
+        context.stroke()?;
+
+        draw_text(
+            context,
+            None,
+            &(left + w / 2.0, y).into(),
+            r#ref,
+            &TextOptions {
+                color: if matches!(name, "white" | "yellow") {
+                    colors::BLACK
+                } else {
+                    colors::WHITE
+                },
+                halo_opacity: 0.0,
+                placements: &[(0.0, 0.0)],
+                flo: FontAndLayoutOptions {
+                    size: SHIELD_FONT_SIZE,
+                    weight: Weight::BOLD,
+                    ..FontAndLayoutOptions::default()
+                },
+                ..TextOptions::default()
+            },
+        )?;
+
+        left += w + GAP;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shields_are_parsed_with_route_colors() {
+        assert_eq!(
+            parse_shields("0:E3;1:EV13;8:X"),
+            vec![
+                ("red", "ff3030", "E3"),
+                ("blue", "5050ff", "EV13"),
+                ("none", "a0a0a0", "X")
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_shields_are_skipped() {
+        assert!(parse_shields("").is_empty());
+    }
+}
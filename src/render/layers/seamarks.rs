@@ -0,0 +1,221 @@
+use crate::render::{
+    Feature,
+    collision::Collision,
+    colors::{self, Color, ContextExt},
+    ctx::Ctx,
+    draw::{
+        font_options::FontAndLayoutOptions,
+        text::{TextOptions, draw_text},
+    },
+    layer_render_error::LayerRenderResult,
+    projectable::TileProjectable,
+};
+use cairo::Context;
+use geo::Rect;
+
+pub async fn query(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    let sql = "
+        SELECT
+            geometry,
+            type,
+            COALESCE(tags->('seamark:' || type || ':category'), '') AS category,
+            COALESCE(tags->('seamark:' || type || ':colour'), '') AS colour,
+            COALESCE(tags->'seamark:light:character', '') AS light_character,
+            COALESCE(tags->'seamark:light:group', '') AS light_group,
+            COALESCE(tags->'seamark:light:colour', '') AS light_colour,
+            COALESCE(tags->'seamark:light:period', '') AS light_period
+        FROM
+            osm_pois
+        WHERE
+            type IN (
+                'buoy_lateral', 'buoy_cardinal', 'buoy_isolated_danger', 'buoy_safe_water',
+                'buoy_special_purpose', 'beacon_lateral', 'beacon_cardinal',
+                'beacon_isolated_danger', 'beacon_safe_water', 'beacon_special_purpose'
+            ) AND
+            geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
+        ORDER BY
+            osm_id
+    ";
+
+    client
+        .query(sql, &ctx.bbox_query_params(Some(128.0)).as_params())
+        .await
+}
+
+pub fn render(
+    ctx: &Ctx,
+    context: &Context,
+    rows: Vec<Feature>,
+    collision: &mut Collision,
+) -> LayerRenderResult {
+    let _span = tracy_client::span!("seamarks::render");
+
+    let text_options = TextOptions {
+        color: colors::WATER_LABEL,
+        halo_color: colors::WATER_LABEL_HALO,
+        flo: FontAndLayoutOptions {
+            size: 10.0,
+            ..FontAndLayoutOptions::default()
+        },
+        placements: &[(0.0, 10.0), (0.0, 13.0)],
+        valign_by_placement: true,
+        ..TextOptions::default()
+    };
+
+    for row in rows {
+        let typ = row.get_string("type")?;
+
+        let point = row.get_point()?.project_to_tile(&ctx.tile_projector);
+
+        let (kind, function) = typ.split_once('_').unwrap_or((typ, ""));
+
+        let bands = bands(
+            function,
+            row.get_string("category")?,
+            row.get_string("colour")?,
+        );
+
+        let (x, y) = (point.x(), point.y());
+
+        // buoys float at the position, beacons stand on it
+        let bbox = if kind == "beacon" {
+            Rect::new((x - 1.5, y - 10.0), (x + 1.5, y))
+        } else {
+            Rect::new((x - 4.0, y - 4.0), (x + 4.0, y + 4.0))
+        };
+
+        context.save()?;
+
+        if kind == "beacon" {
+            context.rectangle(bbox.min().x, bbox.min().y, bbox.width(), bbox.height());
+        } else {
+            context.arc(x, y, bbox.width() / 2.0, 0.0, 2.0 * std::f64::consts::PI);
+        }
+
+        let path = context.copy_path()?;
+
+        context.save()?;
+
+        context.clip();
+
+        let band_height = bbox.height() / bands.len() as f64;
+
+        for (i, &color) in bands.iter().enumerate() {
+            context.rectangle(
+                bbox.min().x,
+                (i as f64).mul_add(band_height, bbox.min().y),
+                bbox.width(),
+                band_height,
+            );
+
+            context.set_source_color(color);
+            context.fill()?;
+        }
+
+        context.restore()?;
+
+        context.append_path(&path);
+        context.set_source_color(colors::BLACK);
+        context.set_line_width(0.75);
+        context.stroke()?;
+
+        context.restore()?;
+
+        let _ = collision.add(bbox);
+
+        let label = light_label(
+            row.get_string("light_character")?,
+            row.get_string("light_group")?,
+            row.get_string("light_colour")?,
+            row.get_string("light_period")?,
+        );
+
+        if !label.is_empty() {
+            draw_text(context, Some(collision), &point, &label, &text_options)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Horizontal color bands of the body from the top, after the IALA region A system.
+fn bands(function: &str, category: &str, colour: &str) -> &'static [Color] {
+    match (function, category) {
+        ("lateral", "port") => &[colors::SEAMARK_RED],
+        ("lateral", "starboard") => &[colors::SEAMARK_GREEN],
+        ("lateral", _) => match colour.split(';').next() {
+            Some("red") => &[colors::SEAMARK_RED],
+            Some("green") => &[colors::SEAMARK_GREEN],
+            _ => &[colors::WHITE],
+        },
+        ("cardinal", "south") => &[colors::SEAMARK_YELLOW, colors::BLACK],
+        ("cardinal", "east") => &[colors::BLACK, colors::SEAMARK_YELLOW, colors::BLACK],
+        ("cardinal", "west") => &[
+            colors::SEAMARK_YELLOW,
+            colors::BLACK,
+            colors::SEAMARK_YELLOW,
+        ],
+        ("cardinal", _) => &[colors::BLACK, colors::SEAMARK_YELLOW],
+        ("isolated_danger", _) => &[colors::BLACK, colors::SEAMARK_RED, colors::BLACK],
+        ("safe_water", _) => &[colors::SEAMARK_RED, colors::WHITE],
+        _ => &[colors::SEAMARK_YELLOW],
+    }
+}
+
+/// Abbreviated light characteristic as printed on nautical charts, e.g. `Fl(2) WR 10s`.
+fn light_label(character: &str, group: &str, colour: &str, period: &str) -> String {
+    if character.is_empty() {
+        return String::new();
+    }
+
+    let mut label = character.to_string();
+
+    if !group.is_empty() {
+        label.push('(');
+        label.push_str(group);
+        label.push(')');
+    }
+
+    let colours: String = colour
+        .split(';')
+        .filter_map(|colour| match colour.trim() {
+            "white" => Some('W'),
+            "red" => Some('R'),
+            "green" => Some('G'),
+            "yellow" => Some('Y'),
+            _ => None,
+        })
+        .collect();
+
+    if !colours.is_empty() {
+        label.push(' ');
+        label.push_str(&colours);
+    }
+
+    if !period.is_empty() {
+        label.push(' ');
+        label.push_str(period);
+        label.push('s');
+    }
+
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn light_label_is_abbreviated() {
+        assert_eq!(light_label("Fl", "2", "white;red", "10"), "Fl(2) WR 10s");
+        assert_eq!(light_label("Q", "", "green", ""), "Q G");
+    }
+
+    #[test]
+    fn light_label_requires_character() {
+        assert_eq!(light_label("", "", "red", "4"), "");
+    }
+}
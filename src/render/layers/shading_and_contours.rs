@@ -2,7 +2,11 @@ use crate::render::{
     ContourCountries, Feature, HillshadingHierarchy,
     ctx::Ctx,
     layer_render_error::LayerRenderResult,
-    layers::{bridge_areas, contours, hillshading, hillshading_datasets::HillshadingDatasets},
+    layers::{
+        bridge_areas, contours,
+        hillshading::{self, TileSurfaces},
+        hillshading_datasets::HillshadingDatasets,
+    },
 };
 use cairo::{Context, Format, ImageSurface, SurfacePattern};
 use std::collections::{HashMap, HashSet};
@@ -13,6 +17,8 @@ pub struct ShadingParams<'a> {
     pub hierarchy: &'a HillshadingHierarchy,
     pub contour_countries: Option<&'a ContourCountries>,
     pub do_shading: bool,
+    /// Surfaces already loaded for the tile by an earlier pass.
+    pub surfaces: Option<TileSurfaces>,
 }
 
 pub fn render(
@@ -29,26 +35,19 @@ pub fn render(
         hierarchy,
         contour_countries,
         do_shading,
+        surfaces,
     } = params;
 
     let fade_alpha = 1.0f64.min(1.0 - (ctx.zoom as f64 - 7.0).ln() / 5.0);
 
     // Load all country mask surfaces once; reused by hillshading and contours.
-    let mut country_masks: Vec<(&'static str, Option<ImageSurface>)> = hierarchy
-        .entries()
-        .iter()
-        .map(|entry| {
-            Ok((
-                entry.country,
-                hillshading::load_surface(
-                    ctx,
-                    entry.country,
-                    hillshading_datasets,
-                    hillshading::Mode::Mask,
-                )?,
-            ))
-        })
-        .collect::<Result<_, crate::render::layer_render_error::LayerRenderError>>()?;
+    let TileSurfaces {
+        masks: mut country_masks,
+        mut shadings,
+    } = match surfaces {
+        Some(surfaces) => surfaces,
+        None => TileSurfaces::load_masks(ctx, hierarchy, hillshading_datasets)?,
+    };
 
     let tile_covered = {
         let mut present_mut: Vec<&mut ImageSurface> = country_masks
@@ -76,13 +75,17 @@ pub fn render(
                 continue;
             };
 
-            let Some(shading_surface) = hillshading::load_surface(
-                ctx,
-                country,
-                hillshading_datasets,
-                hillshading::Mode::Shading,
-            )?
-            else {
+            let shading_surface = match shadings.remove(country) {
+                Some(surface) => surface,
+                None => hillshading::load_surface(
+                    ctx,
+                    country,
+                    hillshading_datasets,
+                    hillshading::Mode::Shading,
+                )?,
+            };
+
+            let Some(shading_surface) = shading_surface else {
                 continue;
             };
 
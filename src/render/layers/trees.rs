@@ -7,6 +7,7 @@ use cairo::Context;
 pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
     let sql = "
         SELECT
+            osm_id,
             type,
             geometry
         FROM
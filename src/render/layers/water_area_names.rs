@@ -13,6 +13,7 @@ use crate::render::{
 };
 use cairo::Context;
 use cosmic_text::Style;
+use geo::{Area, Centroid, Geometry};
 use regex::Regex;
 use std::sync::LazyLock;
 
@@ -23,6 +24,9 @@ pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tok
     let sql = "
         SELECT
             name,
+            type,
+            water,
+            area,
             ST_PointOnSurface(osm_waterareas.geometry) AS geometry
         FROM
             osm_waterareas
@@ -51,24 +55,93 @@ pub fn render(
     let _span = tracy_client::span!("water_area_names::render");
 
     let text_options = TextOptions {
-        flo: FontAndLayoutOptions {
-            style: Style::Italic,
-            ..FontAndLayoutOptions::default()
-        },
         color: colors::WATER_LABEL,
         halo_color: colors::WATER_LABEL_HALO,
         ..TextOptions::default()
     };
 
+    let meters_per_pixel = ctx.meters_per_pixel();
+
     for row in rows {
+        let name = replace(row.get_string("name")?, &REPLACEMENTS);
+
+        let typ = row.get_string("type")?;
+
+        let water = row.get_string("water")?;
+
+        // legend features come as polygons without the area column
+        let (point, area) = match row.get_geometry()? {
+            Geometry::Point(point) => (point, f64::from(row.get_f32("area")?)),
+            Geometry::Polygon(polygon) => (
+                polygon.centroid().expect("centroid"),
+                polygon.unsigned_area(),
+            ),
+            _ => (row.get_point()?, 0.0),
+        };
+
+        let (size, style) = label_style(typ, water, area / meters_per_pixel.powi(2));
+
         draw_text(
             context,
             Some(collision),
-            &row.get_point()?.project_to_tile(&ctx.tile_projector),
-            &replace(row.get_string("name")?, &REPLACEMENTS),
-            &text_options,
+            &point.project_to_tile(&ctx.tile_projector),
+            &name,
+            &TextOptions {
+                flo: FontAndLayoutOptions {
+                    size,
+                    style,
+                    ..text_options.flo
+                },
+                ..text_options
+            },
         )?;
     }
 
     Ok(())
 }
+
+/// Label size and style by the water body kind and its area in square pixels.
+/// Small and artificial waters get a plain small label, natural lakes an italic one growing with
+/// their area.
+fn label_style(typ: &str, water: &str, area_px: f64) -> (f64, Style) {
+    let artificial = matches!(typ, "basin" | "reservoir" | "swimming_pool" | "fountain")
+        || matches!(water, "reservoir" | "basin" | "pond" | "fishpond" | "lagoon");
+
+    let style = if artificial && water != "lagoon" {
+        Style::Normal
+    } else {
+        Style::Italic
+    };
+
+    let size = if matches!(water, "pond" | "fishpond") || typ == "basin" {
+        10.0
+    } else {
+        (10.0 + (area_px / 5_000.0).max(1.0).log2()).clamp(10.0, 15.0)
+    };
+
+    (size, style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ponds_get_small_plain_labels() {
+        assert_eq!(label_style("water", "pond", 1e6), (10.0, Style::Normal));
+        assert_eq!(label_style("basin", "", 1e6), (10.0, Style::Normal));
+    }
+
+    #[test]
+    fn lakes_grow_with_area() {
+        assert_eq!(label_style("water", "lake", 1_000.0), (10.0, Style::Italic));
+        assert_eq!(label_style("water", "lake", 20_000.0), (12.0, Style::Italic));
+        assert_eq!(label_style("water", "", 1e9), (15.0, Style::Italic));
+    }
+
+    #[test]
+    fn reservoirs_are_upright() {
+        assert_eq!(label_style("reservoir", "", 40_000.0), (13.0, Style::Normal));
+        assert_eq!(label_style("water", "reservoir", 40_000.0).1, Style::Normal);
+    }
+}
@@ -2,12 +2,16 @@ use crate::render::{
     Feature,
     colors::{self, ContextExt},
     ctx::Ctx,
-    draw::{markers_on_path::draw_markers_on_path, smooth_line::path_smooth_bezier_spline},
+    draw::{
+        markers_on_path::draw_markers_on_path, path_geom::path_line_string_with_offset,
+        smooth_line::path_smooth_bezier_spline,
+    },
     layer_render_error::LayerRenderResult,
     projectable::TileProjectable,
     svg_repo::SvgRepo,
 };
 use cairo::Context;
+use geo::LineString;
 
 pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
     let geom_query = match ctx.zoom {
@@ -26,10 +30,12 @@ pub async fn query(ctx: &Ctx, client: &tokio_postgres::Client) -> Result<Vec<tok
     #[cfg_attr(any(), rustfmt::skip)]
     let sql = format!("
         SELECT
+            osm_id,
             {geom_query},
             type,
             seasonal OR intermittent AS tmp,
-            tunnel
+            tunnel,
+            COALESCE(towpath, '') AS towpath
         FROM
             {table}
         WHERE
@@ -71,22 +77,12 @@ pub fn render(
             let tmp = row.get_bool("tmp")?;
             let tunnel = row.get_bool("tunnel")?;
 
-            context.set_dash(if tmp { &[6.0, 3.0] } else { &[] }, 0.0);
-
-            let (width, smooth) = match (typ, zoom) {
-                ("river" | "canal", ..=8) => (1.5f64.powf(zoom as f64 - 8.0), 0.0),
-                ("river" | "canal", 9) => (1.5, 0.0),
-                ("river" | "canal", 10..=11) => (2.2, 0.0),
-                ("river" | "canal", 12..) => (2.2, 0.5),
-                (
-                    "canoe_pass" | "ditch" | "drain" | "fish_pass" | "rapids" | "ressurised"
-                    | "stream" | "tidal_channel",
-                    12..,
-                ) => (if zoom == 12 { 1.0 } else { 1.2 }, 0.5),
-
-                _ => continue,
+            let Some((width, smooth)) = width_and_smoothing(typ, zoom) else {
+                continue;
             };
 
+            context.set_dash(dash(tmp, tunnel), 0.0);
+
             if glow {
                 if zoom >= 12 {
                     context.set_source_color(colors::WATER);
@@ -105,6 +101,8 @@ pub fn render(
 
                     context.stroke()?;
                 }
+            } else if typ == "tidal_channel" && !tunnel {
+                draw_tidal_channel(context, &geom, width, smooth)?;
             } else {
                 context.set_source_color_a(colors::WATER, if tunnel { 0.33 } else { 1.0 });
 
@@ -116,16 +114,27 @@ pub fn render(
 
                 context.stroke()?;
 
-                draw_markers_on_path(&path, 150.0, 300.0, &|x, y, angle| -> cairo::Result<()> {
-                    context.save()?;
-                    context.translate(x, y);
-                    context.rotate(angle);
-                    context.set_source_surface(arrow, dx, dy)?;
-                    context.paint()?;
-                    context.restore()?;
+                if has_flow_arrows(typ) {
+                    draw_markers_on_path(
+                        &path,
+                        150.0,
+                        300.0,
+                        &|x, y, angle| -> cairo::Result<()> {
+                            context.save()?;
+                            context.translate(x, y);
+                            context.rotate(angle);
+                            context.set_source_surface(arrow, dx, dy)?;
+                            context.paint()?;
+                            context.restore()?;
 
-                    Ok(())
-                })?;
+                            Ok(())
+                        },
+                    )?;
+                }
+
+                if zoom >= 15 && typ == "canal" && !tunnel {
+                    draw_towpath(context, &geom, row.get_string("towpath")?, width)?;
+                }
             }
         }
     }
@@ -134,3 +143,122 @@ pub fn render(
 
     Ok(())
 }
+
+fn width_and_smoothing(typ: &str, zoom: u8) -> Option<(f64, f64)> {
+    Some(match (typ, zoom) {
+        ("river" | "canal", ..=8) => (1.5f64.powf(zoom as f64 - 8.0), 0.0),
+        ("river" | "canal", 9) => (1.5, 0.0),
+        ("river" | "canal", 10..=11) => (2.2, 0.0),
+        ("river" | "canal", 12..) => (2.2, 0.5),
+        (
+            "canoe_pass" | "ditch" | "drain" | "fish_pass" | "rapids" | "pressurised" | "stream"
+            | "tidal_channel",
+            12..,
+        ) => (if zoom == 12 { 1.0 } else { 1.2 }, 0.5),
+        _ => return None,
+    })
+}
+
+/// Tidal channels flow both ways.
+fn has_flow_arrows(typ: &str) -> bool {
+    typ != "tidal_channel"
+}
+
+/// Tunnels and culverts (including siphons) are dashed regardless of the waterway type.
+const fn dash(tmp: bool, tunnel: bool) -> &'static [f64] {
+    if tunnel {
+        &[3.0, 2.0]
+    } else if tmp {
+        &[6.0, 3.0]
+    } else {
+        &[]
+    }
+}
+
+/// Pale water between two bank lines, without flow arrows.
+fn draw_tidal_channel(
+    context: &Context,
+    geom: &LineString,
+    width: f64,
+    smooth: f64,
+) -> cairo::Result<()> {
+    context.save()?;
+
+    context.set_source_color_a(colors::WATER, 0.4);
+    context.set_line_width(width * 3.0);
+    path_smooth_bezier_spline(context, geom, smooth);
+    context.stroke()?;
+
+    context.set_source_color(colors::WATER);
+    context.set_line_width(0.8);
+
+    for offset in [-1.5 * width, 1.5 * width] {
+        path_line_string_with_offset(context, geom, offset);
+        context.stroke()?;
+    }
+
+    context.restore()?;
+
+    Ok(())
+}
+
+fn draw_towpath(
+    context: &Context,
+    geom: &LineString,
+    towpath: &str,
+    width: f64,
+) -> cairo::Result<()> {
+    let (left, right) = match towpath {
+        "yes" | "both" => (true, true),
+        "left" => (true, false),
+        "right" => (false, true),
+        _ => return Ok(()),
+    };
+
+    let offset = width.mul_add(0.5, 3.0);
+
+    context.save()?;
+
+    context.set_source_color(colors::TRACK);
+    context.set_line_width(0.8);
+    context.set_dash(&[3.0, 3.0], 0.0);
+
+    // offset is positive to the right in the tile (y-down) coordinates
+    for (draw, offset) in [(left, -offset), (right, offset)] {
+        if draw {
+            path_line_string_with_offset(context, geom, offset);
+            context.stroke()?;
+        }
+    }
+
+    context.restore()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn specialized_waterways_are_rendered() {
+        for typ in ["pressurised", "canoe_pass", "fish_pass", "tidal_channel"] {
+            assert!(width_and_smoothing(typ, 12).is_some(), "{typ}");
+            assert!(width_and_smoothing(typ, 17).is_some(), "{typ}");
+        }
+    }
+
+    #[test]
+    fn tidal_channels_have_no_flow_arrows() {
+        assert!(!has_flow_arrows("tidal_channel"));
+        assert!(has_flow_arrows("stream"));
+        assert!(has_flow_arrows("river"));
+    }
+
+    #[test]
+    fn tunnels_are_dashed() {
+        assert!(!dash(false, true).is_empty());
+        assert!(!dash(true, true).is_empty());
+        assert!(dash(false, false).is_empty());
+    }
+}
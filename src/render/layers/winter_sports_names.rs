@@ -0,0 +1,140 @@
+use crate::render::{
+    Feature,
+    collision::Collision,
+    colors,
+    ctx::Ctx,
+    draw::{
+        font_options::FontAndLayoutOptions,
+        text::{TextOptions, draw_text},
+    },
+    feature::{GEOMETRY_COLUMN, WrongTypeError},
+    layer_render_error::{LayerRenderError, LayerRenderResult},
+    projectable::TileProjectable,
+    svg_repo::{Options, SvgRepo},
+};
+use cairo::Context;
+use geo::{Centroid, Geometry, Rect};
+
+pub async fn query(
+    ctx: &Ctx,
+    client: &tokio_postgres::Client,
+) -> Result<Vec<tokio_postgres::Row>, tokio_postgres::Error> {
+    // nested sql is to remove duplicate entries imported by imposm because we use `mappings` in yaml
+    let sql = "
+        WITH main AS (
+            SELECT DISTINCT ON (osm_id)
+                name,
+                geometry,
+                area,
+                osm_id
+            FROM
+                osm_landcovers
+            WHERE
+                type = 'winter_sports' AND
+                name <> '' AND
+                area >= $6 AND
+                geometry && ST_Expand(ST_MakeEnvelope($1, $2, $3, $4, 3857), $5)
+        )
+        SELECT
+            name,
+            ST_PointOnSurface(geometry) AS geometry
+        FROM
+            main
+        ORDER BY
+            area DESC,
+            osm_id
+    ";
+
+    client
+        .query(
+            sql,
+            &ctx.bbox_query_params(Some(512.0))
+                .push(2_400_000.0f32 / (2.0f32 * (ctx.zoom as f32 - 10.0)).exp2())
+                .as_params(),
+        )
+        .await
+}
+
+pub fn render(
+    ctx: &Ctx,
+    context: &Context,
+    rows: Vec<Feature>,
+    collision: &mut Collision,
+    svg_repo: &mut SvgRepo,
+) -> LayerRenderResult {
+    let _span = tracy_client::span!("winter_sports_names::render");
+
+    let surface = svg_repo.get_extra(
+        "winter_sports",
+        Some(|| Options {
+            names: vec!["winter_sports".into()],
+            halo: true,
+            ..Options::default()
+        }),
+    )?;
+
+    let rect = surface.extents().expect("surface extents");
+
+    let hw = rect.width() / 2.0;
+
+    let hh = rect.height() / 2.0;
+
+    let placements = [(0.0, hh + 2.0), (0.0, hh + 4.0), (0.0, hh + 6.0)];
+
+    let text_options = TextOptions {
+        flo: FontAndLayoutOptions {
+            size: 13.0,
+            ..FontAndLayoutOptions::default()
+        },
+        color: colors::WINTER_SPORTS_LABEL,
+        valign_by_placement: true,
+        placements: &placements,
+        ..TextOptions::default()
+    };
+
+    for row in rows {
+        // legend features come as polygons
+        let point = match row.get_geometry()? {
+            Geometry::Point(point) => point,
+            Geometry::Polygon(polygon) => polygon.centroid().expect("centroid"),
+            Geometry::MultiPolygon(mp) => mp.centroid().expect("centroid"),
+            _ => {
+                return Err(LayerRenderError::FeatureError(
+                    crate::render::FeatureError::WrongTypeError(WrongTypeError::new(
+                        GEOMETRY_COLUMN,
+                        "Point, Polygon or Multipolygon",
+                        "?",
+                    )),
+                ));
+            }
+        }
+        .project_to_tile(&ctx.tile_projector);
+
+        let x = ctx.hint(point.x() - hw);
+        let y = ctx.hint(point.y() - hh);
+
+        let bbox = Rect::new((x, y), (x + rect.width(), y + rect.height()));
+
+        if collision.collides(&bbox) {
+            continue;
+        }
+
+        let bbox_idx = collision.add(bbox);
+
+        context.set_source_surface(surface, x - rect.x(), y - rect.y())?;
+        context.paint()?;
+
+        draw_text(
+            context,
+            Some(collision),
+            &point,
+            row.get_string("name")?,
+            &TextOptions {
+                omit_bbox: Some(bbox_idx),
+                ..text_options
+            },
+        )?;
+    }
+
+    Ok(())
+}
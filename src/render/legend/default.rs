@@ -31,11 +31,11 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
             "ditch",
             "drain",
             "rapids",
-            "tidal_channel",
             "pressurised",
             "canoe_pass",
             "fish_pass",
         ],
+        &["tidal_channel"],
     ]
     .iter()
     .map(|types| {
@@ -57,10 +57,27 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
                 .with("type", types[0])
                 .with("tmp", false)
                 .with("tunnel", false)
+                .with("towpath", "")
         })
         .build()
     })
     .chain([
+        LegendItem::builder("waterway_towpath", Category::Water, 17, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("waterway", "canal").add("towpath", "yes"))
+                    .add_tags(|tags| tags.add("waterway", "canal").add("towpath", "left"))
+                    .add_tags(|tags| tags.add("waterway", "canal").add("towpath", "right"))
+                    .add_tags(|tags| tags.add("waterway", "canal").add("towpath", "both"))
+            })
+            .add_feature("water_lines", |b| {
+                b.with_line_string(false)
+                    .with_name()
+                    .with("type", "canal")
+                    .with("tmp", false)
+                    .with("tunnel", false)
+                    .with("towpath", "both")
+            })
+            .build(),
         LegendItem::builder("waterway_tmp", Category::Water, 17, for_taginfo)
             .add_tag_set(|ts| {
                 ts.add_tags(|tags| tags.add("waterway", "*").add("intermittent", "yes"))
@@ -72,16 +89,21 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
                     .with("type", "stream")
                     .with("tmp", true)
                     .with("tunnel", false)
+                    .with("towpath", "")
             })
             .build(),
         LegendItem::builder("waterway_culvert", Category::Water, 17, for_taginfo)
-            .add_tag_set(|ts| ts.add_tags(|tags| tags.add("tunnel", "culvert")))
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("waterway", "*").add("tunnel", "culvert"))
+                    .add_tags(|tags| tags.add("waterway", "*").add("tunnel", "yes"))
+            })
             .add_feature("water_lines", |b| {
                 b.with_line_string(false)
                     .with_name()
                     .with("type", "stream")
                     .with("tmp", false)
                     .with("tunnel", true)
+                    .with("towpath", "")
             })
             .build(),
         LegendItem::builder("water_area", Category::Water, 17, for_taginfo)
@@ -96,7 +118,25 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
                     .add_tags(|tags| tags.add("waterway", "waterway"))
             })
             .add_feature("water_areas", |b| {
-                b.with_polygon(true).with_name().with("tmp", false)
+                b.with_polygon(true)
+                    .with_name()
+                    .with("type", "water")
+                    .with("water", "lake")
+                    .with("tmp", false)
+            })
+            .build(),
+        LegendItem::builder("water_area_reservoir", Category::Water, 17, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("landuse", "reservoir"))
+                    .add_tags(|tags| tags.add("natural", "water").add("water", "reservoir"))
+                    .add_tags(|tags| tags.add("natural", "water").add("water", "pond"))
+            })
+            .add_feature("water_areas", |b| {
+                b.with_polygon(true)
+                    .with_name()
+                    .with("type", "water")
+                    .with("water", "reservoir")
+                    .with("tmp", false)
             })
             .build(),
         LegendItem::builder("water_area_tmp", Category::Water, 17, for_taginfo)
@@ -105,9 +145,23 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
                     .add_tags(|tags| tags.add("natural", "water").add("seasonal", "yes"))
             })
             .add_feature("water_areas", |b| {
-                b.with_polygon(true).with_name().with("tmp", true)
+                b.with_polygon(true)
+                    .with_name()
+                    .with("type", "water")
+                    .with("water", "")
+                    .with("tmp", true)
             })
             .build(),
+        LegendItem::builder("reef", Category::Water, 17, for_taginfo)
+            .add_tag_set(|ts| ts.add_tags(|tags| tags.add("natural", "reef")))
+            .add_feature("water_areas", |b| b.with_polygon(true).with("tmp", false))
+            .add_feature("reefs", |b| b.with_polygon(true))
+            .build(),
+        LegendItem::builder("bridge_area", Category::Landcover, 17, for_taginfo)
+            .add_tag_set(|ts| ts.add_tags(|tags| tags.add("man_made", "bridge")))
+            .add_feature("water_areas", |b| b.with_polygon(true).with("tmp", false))
+            .add_feature("bridge_areas", |b| b.with_polygon(false))
+            .build(),
         LegendItem::builder("solar_power_plants", Category::Landcover, 17, for_taginfo)
             .add_tag_set(|ts| {
                 ts.add_tags(|tags| tags.add("power", "plant").add("plant:source", "solar"))
@@ -125,6 +179,13 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
             })
             .add_feature("special_parks", |b| b.with_polygon(true))
             .build(),
+        LegendItem::builder("winter_sports", Category::Sport, 17, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("landuse", "winter_sports").add("name", "*"))
+            })
+            .add_landcover("winter_sports")
+            .add_feature("winter_sports_names", |b| b.with_name().with_polygon(true))
+            .build(),
         LegendItem::builder("country_borders", Category::Borders, 17, for_taginfo)
             .add_tag_set(|ts| {
                 ts.add_tags(|tags| {
@@ -133,7 +194,22 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
                         .add("admin_level", "2")
                 })
             })
-            .add_feature("country_borders", |b| b.with_polygon(true))
+            .add_feature("country_borders", |b| {
+                b.with_polygon(true).with("maritime", false)
+            })
+            .build(),
+        LegendItem::builder("maritime_borders", Category::Borders, 17, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| {
+                    tags.add("type", "boundary")
+                        .add("boundary", "administrative")
+                        .add("admin_level", "2")
+                        .add("maritime", "yes")
+                })
+            })
+            .add_feature("country_borders", |b| {
+                b.with_line_string(false).with("maritime", true)
+            })
             .build(),
         LegendItem::builder("military_areas", Category::Borders, 17, for_taginfo)
             .add_tag_set(|ts| ts.add_tags(|tags| tags.add("landuse", "military")))
@@ -188,6 +264,19 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
             .add_tag_set(|ts| ts.add_tags(|tags| tags.add("building", "*")))
             .add_feature("buildings", |b| b.with("type", "yes").with_polygon(false))
             .build(),
+        LegendItem::builder("building_roof", Category::Other, 19, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("building", "*").add("roof:shape", "*"))
+                    .add_tags(|tags| tags.add("building:part", "*").add("roof:shape", "*"))
+            })
+            .add_feature("buildings", |b| {
+                b.with("type", "yes")
+                    .with("colour", "")
+                    .with("roof_shape", "gabled")
+                    .with("roof_colour", "")
+                    .with_polygon(false)
+            })
+            .build(),
         LegendItem::builder("building_disused", Category::Other, 17, for_taginfo)
             .add_tag_set(|ts| {
                 ts.add_tags(|tags| tags.add("building", "disused"))
@@ -220,6 +309,19 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
             .add_tag_set(|ts| ts.add_tags(|tags| tags.add("fixme", "*")))
             .add_feature("fixmes", |b| b.with("geometry", Point::new(0.0, 0.0)))
             .build(),
+        LegendItem::builder("island", Category::Terrain, 17, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("place", "island"))
+                    .add_tags(|tags| tags.add("place", "islet"))
+            })
+            .add_feature("water_areas", |b| b.with_polygon(true).with("tmp", false))
+            .add_feature("place_names", |b| {
+                b.with_name()
+                    .with("type", "island")
+                    .with("area", 0.0f32)
+                    .with("geometry", Point::new(0.0, 0.0))
+            })
+            .build(),
         LegendItem::builder("simple_tree", Category::NaturalPoi, 17, for_taginfo)
             .add_tag_set(|ts| ts.add_tags(|tags| tags.add("natural", "tree")))
             .add_feature("trees", |b| {
@@ -234,7 +336,80 @@ pub(super) fn build_legend_items(for_taginfo: bool) -> Vec<LegendItem<'static>>
                     .with("geometry", Point::new(0.0, 0.0))
             })
             .build(),
-    ]);
+    ])
+    .chain(["fairway", "tee", "green", "bunker"].map(|typ| {
+        LegendItem::builder(
+            format!("golf_{typ}").leak(),
+            Category::Sport,
+            19,
+            for_taginfo,
+        )
+        .add_tag_set(|ts| ts.add_tags(|tags| tags.add("golf", typ)))
+        .add_landcover("golf_course")
+        .add_feature("golf", |b| {
+            b.with("type", format!("golf_{typ}").leak() as &str)
+                .with_polygon(false)
+        })
+        .build()
+    }))
+    .chain(["soccer", "basketball", "tennis"].map(|sport| {
+        LegendItem::builder(
+            format!("pitch_sport_{sport}").leak(),
+            Category::Sport,
+            19,
+            for_taginfo,
+        )
+        .add_tag_set(|ts| ts.add_tags(|tags| tags.add("leisure", "pitch").add("sport", sport)))
+        .add_landcover("pitch")
+        .add_feature("pitch_markings", |b| {
+            b.with("sport", sport).with_polygon(false)
+        })
+        .build()
+    }))
+    .chain(
+        [
+            ("buoy_lateral", "port", ("Fl", "", "red", "4")),
+            ("buoy_lateral", "starboard", ("", "", "", "")),
+            ("buoy_cardinal", "north", ("Q", "", "white", "")),
+            ("buoy_isolated_danger", "", ("Fl", "2", "white", "5")),
+            ("buoy_safe_water", "", ("", "", "", "")),
+            ("buoy_special_purpose", "", ("", "", "", "")),
+            ("beacon_lateral", "port", ("", "", "", "")),
+            ("beacon_cardinal", "north", ("", "", "", "")),
+        ]
+        .map(|(typ, category, (character, group, colour, period))| {
+            let id = if category.is_empty() {
+                format!("seamark_{typ}")
+            } else {
+                format!("seamark_{typ}_{category}")
+            };
+
+            LegendItem::builder(id.leak(), Category::Water, 17, for_taginfo)
+                .add_tag_set(|ts| {
+                    ts.add_tags(|tags| {
+                        let tags = tags.add("seamark:type", typ);
+
+                        if category.is_empty() {
+                            tags
+                        } else {
+                            tags.add(format!("seamark:{typ}:category").leak(), category)
+                        }
+                    })
+                })
+                .add_feature("water_areas", |b| b.with_polygon(true).with("tmp", false))
+                .add_feature("seamarks", |b| {
+                    b.with("type", typ)
+                        .with("category", category)
+                        .with("colour", "")
+                        .with("light_character", character)
+                        .with("light_group", group)
+                        .with("light_colour", colour)
+                        .with("light_period", period)
+                        .with("geometry", Point::new(0.0, 0.0))
+                })
+                .build()
+        }),
+    );
 
     poi_items
         .into_iter()
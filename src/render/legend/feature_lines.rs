@@ -18,12 +18,14 @@ pub fn feature_lines(
         (&["pipeline_under"], Category::Other),
         (&["tree_row"], Category::Other),
         (&["weir"], Category::Water),
+        (&["lock_gate"], Category::Water),
         (&["dam"], Category::Water),
         (&["earth_bank"], Category::Terrain),
         (&["dyke"], Category::Terrain),
         (&["embankment"], Category::Terrain),
         (&["gully"], Category::Terrain),
         (&["cliff"], Category::Terrain),
+        (&["sinkhole"], Category::Terrain),
         (
             &["runway", "taxiway", "parking_position", "taxilane"],
             Category::Other,
@@ -51,6 +53,10 @@ pub fn feature_lines(
             ],
             Category::Other,
         ),
+        (
+            &["summer_toboggan", "alpine_coaster", "roller_coaster"],
+            Category::Sport,
+        ),
     ];
 
     groups
@@ -78,8 +84,15 @@ pub fn feature_lines(
 
                     let mut tags = IndexMap::new();
 
+                    // sinkhole areas are mapped as landcovers
+                    let table = if typ == "sinkhole" {
+                        "landcovers"
+                    } else {
+                        "feature_lines"
+                    };
+
                     for entry in mapping_entries {
-                        if entry.table == "feature_lines" && entry.value == typ {
+                        if entry.table == table && entry.value == typ {
                             let value = leak_str(&entry.value);
                             let key = leak_str(&entry.key);
 
@@ -119,7 +132,8 @@ pub fn feature_lines(
             })
             .add_landcover("meadow")
             .add_feature("feature_lines", |b| {
-                b.with("name", if types[0] == "cable_car" { "Abc" } else { "" }) // NOTE only aerialways have name
+                let b = b
+                    .with("name", if types[0] == "cable_car" { "Abc" } else { "" }) // NOTE only aerialways have name
                     .with(
                         "type",
                         if types[0] == "pipeline_under" {
@@ -129,6 +143,7 @@ pub fn feature_lines(
                         },
                     )
                     .with("class", "highway")
+                    .with("flow_side", i16::from(types[0] == "weir"))
                     .with(
                         "tags",
                         if types[0] == "pipeline_under" {
@@ -136,8 +151,13 @@ pub fn feature_lines(
                         } else {
                             HashMap::new()
                         },
-                    )
-                    .with_line_string(false)
+                    );
+
+                if types[0] == "sinkhole" {
+                    b.with_polygon_ring(false)
+                } else {
+                    b.with_line_string(false)
+                }
             });
 
             if types[0] == "line" {
@@ -72,7 +72,7 @@ fn build_landcover_tags(
 
     if matches!(
         typ,
-        "bog" | "reedbed" | "marsh" | "swamp" | "wet_meadow" | "mangrove" | "fen"
+        "bog" | "reedbed" | "marsh" | "swamp" | "wet_meadow" | "mangrove" | "fen" | "tidalflat"
     ) {
         tags.push(("natural", "wetland"));
         tags.push(("wetland", typ));
@@ -224,10 +224,13 @@ pub fn legend_render_request(id: &str, scale: f64, mode: LegendMode) -> Option<R
         },
         HashSet::from([
             RenderLayer::CountryBorders,
+            RenderLayer::MaritimeBorders,
             RenderLayer::RoutesBicycle,
             RenderLayer::RoutesHiking,
             RenderLayer::RoutesHorse,
             RenderLayer::RoutesSki,
+            RenderLayer::RoofShading,
+            RenderLayer::Seamarks,
         ]),
         None,
     );
@@ -239,14 +242,36 @@ pub fn legend_render_request(id: &str, scale: f64, mode: LegendMode) -> Option<R
 
 impl PropsBuilder {
     pub fn with_line_string(self, reverse: bool) -> Self {
-        let mut coords = if self.for_taginfo {
+        let mut coords = self.line_string_coords();
+
+        if reverse {
+            coords.reverse();
+        }
+
+        self.with("geometry", LineString::new(coords.to_vec()))
+    }
+
+    /// Part of the default legend line between the `start` and `end` fractions of its length.
+    pub fn with_line_string_part(self, start: f64, end: f64) -> Self {
+        let [from, to] = self.line_string_coords();
+
+        let at = |t: f64| Coord {
+            x: (to.x - from.x).mul_add(t, from.x),
+            y: (to.y - from.y).mul_add(t, from.y),
+        };
+
+        self.with("geometry", LineString::new(vec![at(start), at(end)]))
+    }
+
+    fn line_string_coords(&self) -> [Coord; 2] {
+        if self.for_taginfo {
             let px = 10.0 * to_px(self.zoom);
 
-            vec![Coord { x: px, y: 0.0 }, Coord { x: -px, y: 0.0 }]
+            [Coord { x: px, y: 0.0 }, Coord { x: -px, y: 0.0 }]
         } else {
             let factor = (17.0 - self.zoom as f64).exp2();
 
-            vec![
+            [
                 Coord {
                     x: 80.0 * factor,
                     y: 20.0 * factor,
@@ -256,67 +281,67 @@ impl PropsBuilder {
                     y: -20.0 * factor,
                 },
             ]
-        };
-
-        if reverse {
-            coords.reverse();
         }
-
-        self.with("geometry", LineString::new(coords))
     }
 
     pub fn with_polygon(self, skew: bool) -> Self {
+        let ring = self.polygon_ring(skew);
+
+        self.with("geometry", Polygon::new(ring, vec![]))
+    }
+
+    /// Clockwise outer ring of the default legend polygon as a line string.
+    pub fn with_polygon_ring(self, skew: bool) -> Self {
+        let ring = self.polygon_ring(skew);
+
+        self.with("geometry", ring)
+    }
+
+    fn polygon_ring(&self, skew: bool) -> LineString {
         let zoom = self.zoom;
-        let for_taginfo = self.for_taginfo;
 
         let px = 7.0 * to_px(zoom);
 
-        self.with(
-            "geometry",
-            Polygon::new(
-                if for_taginfo {
-                    LineString::new(vec![
-                        Coord { x: -px, y: -px },
-                        Coord { x: -px, y: px },
-                        Coord { x: px, y: px },
-                        Coord { x: px, y: -px },
-                        Coord { x: -px, y: -px },
-                    ])
-                } else {
-                    let factor = (19.0 - zoom as f64).exp2();
-
-                    let ssx = if skew { 2.0 } else { 0.0 };
-                    let ssy = if skew { 1.0 } else { 0.0 };
-
-                    let xx = 12.0;
-                    let yy = 5.0;
-
-                    LineString::new(vec![
-                        Coord {
-                            x: factor * -xx,
-                            y: factor * (-yy - ssy),
-                        },
-                        Coord {
-                            x: factor * (-xx - ssx),
-                            y: factor * yy,
-                        },
-                        Coord {
-                            x: factor * xx,
-                            y: factor * (yy + ssy),
-                        },
-                        Coord {
-                            x: factor * (xx + ssx),
-                            y: factor * -yy,
-                        },
-                        Coord {
-                            x: factor * -xx,
-                            y: factor * (-yy - ssy),
-                        },
-                    ])
+        if self.for_taginfo {
+            LineString::new(vec![
+                Coord { x: -px, y: -px },
+                Coord { x: -px, y: px },
+                Coord { x: px, y: px },
+                Coord { x: px, y: -px },
+                Coord { x: -px, y: -px },
+            ])
+        } else {
+            let factor = (19.0 - zoom as f64).exp2();
+
+            let ssx = if skew { 2.0 } else { 0.0 };
+            let ssy = if skew { 1.0 } else { 0.0 };
+
+            let xx = 12.0;
+            let yy = 5.0;
+
+            LineString::new(vec![
+                Coord {
+                    x: factor * -xx,
+                    y: factor * (-yy - ssy),
+                },
+                Coord {
+                    x: factor * (-xx - ssx),
+                    y: factor * yy,
+                },
+                Coord {
+                    x: factor * xx,
+                    y: factor * (yy + ssy),
                 },
-                vec![],
-            ),
-        )
+                Coord {
+                    x: factor * (xx + ssx),
+                    y: factor * -yy,
+                },
+                Coord {
+                    x: factor * -xx,
+                    y: factor * (-yy - ssy),
+                },
+            ])
+        }
     }
 }
 
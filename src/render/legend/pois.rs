@@ -79,7 +79,13 @@ pub fn pois(
     let mut poi_groups: PoiGroups = IndexMap::new();
 
     for typ in POI_ORDER.iter() {
-        if *typ == "guidepost_noname" || typ.starts_with("peak") && typ.len() == 5 {
+        // prominence tiers share the legend item of their base type
+        if *typ == "guidepost_noname"
+            || ["peak", "volcano"].iter().any(|base| {
+                typ.strip_prefix(base)
+                    .is_some_and(|tier| tier.len() == 1 && tier.chars().all(|c| c.is_ascii_digit()))
+            })
+        {
             continue;
         }
 
@@ -91,11 +97,7 @@ pub fn pois(
             continue;
         };
 
-        let visual_key = if *typ == "volcano" {
-            typ
-        } else {
-            def.icon_key(typ)
-        };
+        let visual_key = def.icon_key(typ);
 
         let entry = poi_groups
             .entry(visual_key)
@@ -184,6 +186,236 @@ pub fn pois(
                 .build()
             }),
         )
+        .chain(["yes", "no"].map(|drinking_water| {
+            LegendItem::builder(
+                format!("poi_fountain_drinking_water_{drinking_water}").leak(),
+                Category::Water,
+                19,
+                for_taginfo,
+            )
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| {
+                    tags.add("amenity", "fountain")
+                        .add("drinking_water", drinking_water)
+                })
+            })
+            .add_poi(
+                "fountain",
+                HashMap::<String, Option<String>>::from([(
+                    "drinkable".to_string(),
+                    Some(drinking_water.to_string()),
+                )]),
+                Category::Water,
+            )
+            .build()
+        }))
+        .chain(
+            [
+                ("seasonal", "yes"),
+                ("operational_status", "broken"),
+                ("fountain", "bubbler"),
+            ]
+            .map(|(tag_key, tag_value)| {
+                LegendItem::builder(
+                    format!("poi_drinking_water_{tag_key}_{tag_value}").leak(),
+                    Category::Water,
+                    19,
+                    for_taginfo,
+                )
+                .add_tag_set(|ts| {
+                    ts.add_tags(|tags| {
+                        tags.add("amenity", "drinking_water")
+                            .add(tag_key, tag_value)
+                    })
+                })
+                .add_poi(
+                    "drinking_water",
+                    HashMap::<String, Option<String>>::from([(
+                        tag_key.to_string(),
+                        Some(tag_value.to_string()),
+                    )]),
+                    Category::Water,
+                )
+                .build()
+            }),
+        )
+        .chain(
+            [("locked", "yes"), ("access", "private")].map(|(tag_key, tag_value)| {
+                LegendItem::builder(
+                    format!("poi_gate_{tag_key}_{tag_value}").leak(),
+                    Category::Poi,
+                    19,
+                    for_taginfo,
+                )
+                .add_tag_set(|ts| {
+                    ts.add_tags(|tags| tags.add("barrier", "gate").add(tag_key, tag_value))
+                })
+                .add_poi(
+                    "gate",
+                    HashMap::<String, Option<String>>::from([(
+                        tag_key.to_string(),
+                        Some(tag_value.to_string()),
+                    )]),
+                    Category::Poi,
+                )
+                .build()
+            }),
+        )
+        .chain(
+            [("fee", "yes"), ("wheelchair", "yes")].map(|(tag_key, tag_value)| {
+                LegendItem::builder(
+                    format!("poi_toilets_{tag_key}_{tag_value}").leak(),
+                    Category::Poi,
+                    19,
+                    for_taginfo,
+                )
+                .add_tag_set(|ts| {
+                    ts.add_tags(|tags| tags.add("amenity", "toilets").add(tag_key, tag_value))
+                })
+                .add_poi(
+                    "toilets",
+                    HashMap::<String, Option<String>>::from([(
+                        tag_key.to_string(),
+                        Some(tag_value.to_string()),
+                    )]),
+                    Category::Poi,
+                )
+                .build()
+            }),
+        )
+        .chain([{
+            LegendItem::builder(
+                "poi_alpine_hut_winter_room_yes",
+                Category::Accommodation,
+                19,
+                for_taginfo,
+            )
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("tourism", "alpine_hut").add("winter_room", "yes"))
+            })
+            .add_poi(
+                "alpine_hut",
+                HashMap::<String, Option<String>>::from([(
+                    "winter_room".to_string(),
+                    Some("yes".to_string()),
+                )]),
+                Category::Accommodation,
+            )
+            .build()
+        }])
+        .chain(
+            [("shelter", "yes"), ("access", "private")].map(|(tag_key, tag_value)| {
+                LegendItem::builder(
+                    format!("poi_hunting_stand_{tag_key}_{tag_value}").leak(),
+                    Category::Accommodation,
+                    19,
+                    for_taginfo,
+                )
+                .add_tag_set(|ts| {
+                    ts.add_tags(|tags| tags.add("amenity", "hunting_stand").add(tag_key, tag_value))
+                })
+                .add_poi(
+                    "hunting_stand",
+                    HashMap::<String, Option<String>>::from([(
+                        tag_key.to_string(),
+                        Some(tag_value.to_string()),
+                    )]),
+                    Category::Accommodation,
+                )
+                .build()
+            }),
+        )
+        .chain(["collapsed", "sealed"].map(|state| {
+            LegendItem::builder(
+                format!("poi_adit_{state}_yes").leak(),
+                Category::Poi,
+                19,
+                for_taginfo,
+            )
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("man_made", "adit").add(state, "yes"))
+                    .add_tags(|tags| tags.add("man_made", "mineshaft").add(state, "yes"))
+            })
+            .add_poi(
+                "adit",
+                HashMap::<String, Option<String>>::from([(
+                    "mine_state".to_string(),
+                    Some(state.to_string()),
+                )]),
+                Category::Poi,
+            )
+            .build()
+        }))
+        .chain([{
+            LegendItem::builder(
+                "poi_cave_entrance_entrance_secondary",
+                Category::NaturalPoi,
+                19,
+                for_taginfo,
+            )
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| {
+                    tags.add("natural", "cave_entrance")
+                        .add("entrance", "secondary")
+                })
+            })
+            .add_poi(
+                "cave_entrance",
+                HashMap::<String, Option<String>>::from([
+                    ("entrance".to_string(), Some("secondary".to_string())),
+                    ("depth".to_string(), Some("120".to_string())),
+                ]),
+                Category::NaturalPoi,
+            )
+            .build()
+        }])
+        .chain([{
+            LegendItem::builder("poi_stone_height", Category::NaturalPoi, 19, for_taginfo)
+                .add_tag_set(|ts| {
+                    ts.add_tags(|tags| tags.add("natural", "stone").add("height", "*"))
+                        .add_tags(|tags| tags.add("natural", "rock").add("height", "*"))
+                })
+                .add_poi(
+                    "stone",
+                    HashMap::<String, Option<String>>::from([(
+                        "height".to_string(),
+                        Some("6".to_string()),
+                    )]),
+                    Category::NaturalPoi,
+                )
+                .build()
+        }])
+        .chain(["church", "chapel"].map(|building| {
+            LegendItem::builder(
+                format!("poi_{building}_denomination_orthodox").leak(),
+                Category::Institution,
+                19,
+                for_taginfo,
+            )
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| {
+                    tags.add("amenity", "place_of_worship")
+                        .add("building", building)
+                        .add("religion", "christian")
+                        .add("denomination", "orthodox")
+                })
+                .add_tags(|tags| {
+                    tags.add("amenity", "place_of_worship")
+                        .add("building", building)
+                        .add("religion", "christian")
+                        .add("denomination", "greek_catholic")
+                })
+            })
+            .add_poi(
+                building,
+                HashMap::<String, Option<String>>::from([
+                    ("religion".to_string(), Some("christian".to_string())),
+                    ("denomination".to_string(), Some("orthodox".to_string())),
+                ]),
+                Category::Institution,
+            )
+            .build()
+        }))
         .chain([{
             LegendItem::builder("private_poi", Category::Other, 19, for_taginfo)
                 .add_tag_set(|ts| {
@@ -240,6 +472,14 @@ fn build_poi_tags(
                 tags.push(("man_made", a));
                 tags.push(("tower:type", b));
             }
+            s if typ.starts_with("artwork_") => {
+                override_key = Some("artwork");
+                tags.push(("artwork_type", &s[8..]));
+            }
+            s if typ.starts_with("vending_machine_") => {
+                override_key = Some("vending_machine");
+                tags.push(("vending", &s[16..]));
+            }
             "tree_protected" => {
                 override_key = Some("tree");
                 tags.push(("protected", "yes"));
@@ -254,6 +494,9 @@ fn build_poi_tags(
             "church" | "chapel" | "synagogue" | "mosque" | "cathedral" => {
                 tags.push(("building", typ));
             }
+            "multisport" => {
+                override_key = Some("multi");
+            }
             "disused_mine" | "disused_adit" | "disused_mineshaft" => {
                 override_key = Some(&typ[8..]);
                 tags.push(("disused", "yes"));
@@ -4,7 +4,7 @@ use crate::render::{
     legend::{LegendItem, PropsBuilder},
 };
 use indexmap::IndexMap;
-use std::fmt::Write as _;
+use std::{collections::HashMap, fmt::Write as _};
 
 pub fn roads(for_taginfo: bool) -> Vec<LegendItem<'static>> {
     [
@@ -160,6 +160,45 @@ pub fn roads(for_taginfo: bool) -> Vec<LegendItem<'static>> {
             b.with_road("construction").with("class", "highway")
         })
         .build(),
+        LegendItem::builder(
+            "road_construction_class",
+            Category::RoadsAndPaths,
+            17,
+            for_taginfo,
+        )
+        .add_tag_set(|ts| {
+            ts.add_tags(|tags| {
+                tags.add("highway", "construction")
+                    .add("construction", "motorway")
+            })
+            .add_tags(|tags| {
+                tags.add("highway", "construction")
+                    .add("construction", "trunk")
+            })
+        })
+        .add_landcover("residential")
+        .add_feature("roads", |b| {
+            b.with_road("construction")
+                .with("class", "highway")
+                .with("construction", "motorway")
+        })
+        .build(),
+        LegendItem::builder("road_path_dyke", Category::RoadsAndPaths, 17, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("man_made", "dyke").add("highway", "path"))
+                    .add_tags(|tags| tags.add("man_made", "embankment").add("highway", "path"))
+            })
+            .add_landcover("meadow")
+            .add_feature("feature_lines", |b| {
+                b.with("name", "")
+                    .with("type", "dyke")
+                    .with("class", "man_made")
+                    .with("tags", HashMap::<String, Option<String>>::new())
+                    .with_line_string(false)
+            })
+            .add_feature("embankment_top_paths", |b| b.with_line_string(false))
+            .add_feature("roads", |b| b.with_road("path").with("class", "highway"))
+            .build(),
         LegendItem::builder("route_hiking", Category::RoadsAndPaths, 17, for_taginfo)
             .add_tag_set(|ts| {
                 ts.add_tags(|tags| {
@@ -273,6 +312,33 @@ pub fn roads(for_taginfo: bool) -> Vec<LegendItem<'static>> {
                     .with("r_red", 1i32)
             })
             .build(),
+        LegendItem::builder("route_shield", Category::RoadsAndPaths, 17, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| {
+                    tags.add("type", "route")
+                        .add("route", "bicycle")
+                        .add("network", "icn")
+                })
+                .add_tags(|tags| {
+                    tags.add("type", "route")
+                        .add("route", "hiking")
+                        .add("network", "iwn")
+                })
+            })
+            .add_landcover("wood")
+            .add_feature("roads", |b| {
+                b.with_road("track")
+                    .with("name", "")
+                    .with("class", "highway")
+                    .with("tracktype", "grade3")
+            })
+            .add_feature("routes", |b| {
+                b.with_route(true)
+                    .with("off2", 1i32)
+                    .with("b_blue", 1i32)
+                    .with("shields", "1:EV13")
+            })
+            .build(),
     ])
     .chain((1..=5).map(|grade| {
         let grade: &str = format!("grade{grade}").leak();
@@ -319,11 +385,72 @@ pub fn roads(for_taginfo: bool) -> Vec<LegendItem<'static>> {
                 .add_feature("roads", |b| {
                     b.with_road("path")
                         .with("class", "highway")
-                        .with("trail_visibility", i as i32)
+                        .with("trail_visibility", i as i32 + 1)
                 })
                 .build()
             }),
     )
+    .chain(
+        [
+            "hiking",
+            "mountain_hiking",
+            "demanding_mountain_hiking",
+            "alpine_hiking",
+            "demanding_alpine_hiking",
+            "difficult_alpine_hiking",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, sac_scale)| {
+            LegendItem::builder(
+                format!("road_sac_scale_{sac_scale}").leak(),
+                Category::RoadsAndPaths,
+                17,
+                for_taginfo,
+            )
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("highway", "path").add("sac_scale", sac_scale))
+            })
+            .add_landcover("wood")
+            .add_feature("roads", |b| {
+                b.with_road("path")
+                    .with("class", "highway")
+                    .with("sac_scale", i as i32 + 1)
+            })
+            .build()
+        }),
+    )
+    .chain(
+        [("hard", "asphalt", ""), ("rough", "ground", "horrible")].map(
+            |(suitability, surface, smoothness)| {
+                LegendItem::builder(
+                    format!("road_bridleway_surface_{suitability}").leak(),
+                    Category::RoadsAndPaths,
+                    17,
+                    for_taginfo,
+                )
+                .add_tag_set(|ts| {
+                    ts.add_tags(|tags| {
+                        let tags = tags.add("highway", "bridleway").add("surface", surface);
+
+                        if smoothness.is_empty() {
+                            tags
+                        } else {
+                            tags.add("smoothness", smoothness)
+                        }
+                    })
+                })
+                .add_landcover("wood")
+                .add_feature("roads", |b| {
+                    b.with_road("bridleway")
+                        .with("class", "highway")
+                        .with("surface", surface)
+                        .with("smoothness", smoothness)
+                })
+                .build()
+            },
+        ),
+    )
     .chain(
         [
             &["rail"] as &[&str],
@@ -373,6 +500,13 @@ pub fn roads(for_taginfo: bool) -> Vec<LegendItem<'static>> {
         }),
     )
     .chain([
+        LegendItem::builder("road_corridor", Category::RoadsAndPaths, 19, for_taginfo)
+            .add_tag_set(|ts| ts.add_tags(|tags| tags.add("highway", "corridor")))
+            .add_landcover("residential")
+            .add_feature("roads", |b| {
+                b.with_road("corridor").with("class", "highway")
+            })
+            .build(),
         LegendItem::builder("railway_bridge", Category::Railway, 17, for_taginfo)
             .add_tag_set(|ts| ts.add_tags(|tags| tags.add("railway", "rail").add("bridge", "yes")))
             .add_landcover("residential")
@@ -391,6 +525,87 @@ pub fn roads(for_taginfo: bool) -> Vec<LegendItem<'static>> {
                     .with("tunnel", 1i16)
             })
             .build(),
+        LegendItem::builder(
+            "road_tunnel_portal",
+            Category::RoadsAndPaths,
+            17,
+            for_taginfo,
+        )
+        .add_tag_set(|ts| ts.add_tags(|tags| tags.add("highway", "*").add("tunnel", "yes")))
+        .add_landcover("wood")
+        .add_feature("roads", |b| {
+            b.with_road("secondary")
+                .with("class", "highway")
+                .with_line_string_part(0.0, 0.3)
+        })
+        .add_feature("roads", |b| {
+            b.with_road("secondary")
+                .with("class", "highway")
+                .with("tunnel", 1i16)
+                .with_line_string_part(0.3, 0.7)
+        })
+        .add_feature("roads", |b| {
+            b.with_road("secondary")
+                .with("class", "highway")
+                .with_line_string_part(0.7, 1.0)
+        })
+        .build(),
+        LegendItem::builder("road_conveying", Category::RoadsAndPaths, 18, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("highway", "footway").add("conveying", "*"))
+                    .add_tags(|tags| tags.add("highway", "steps").add("conveying", "*"))
+            })
+            .add_landcover("residential")
+            .add_feature("roads", |b| {
+                b.with_road("steps")
+                    .with("class", "highway")
+                    .with("conveying", "forward")
+            })
+            .build(),
+        LegendItem::builder("piste_nordic", Category::RoadsAndPaths, 17, for_taginfo)
+            .add_tag_set(|ts| ts.add_tags(|tags| tags.add("piste:type", "nordic")))
+            .add_landcover("wood")
+            .add_feature("roads", |b| {
+                b.with_road("nordic")
+                    .with("class", "piste:type")
+                    .with("piste_grooming", "classic")
+            })
+            .build(),
+        LegendItem::builder(
+            "piste_nordic_backcountry",
+            Category::RoadsAndPaths,
+            17,
+            for_taginfo,
+        )
+        .add_tag_set(|ts| {
+            ts.add_tags(|tags| {
+                tags.add("piste:type", "nordic")
+                    .add("piste:grooming", "backcountry")
+            })
+        })
+        .add_landcover("wood")
+        .add_feature("roads", |b| {
+            b.with_road("nordic")
+                .with("class", "piste:type")
+                .with("piste_grooming", "backcountry")
+        })
+        .build(),
+        LegendItem::builder("path_on_dyke", Category::RoadsAndPaths, 17, for_taginfo)
+            .add_tag_set(|ts| {
+                ts.add_tags(|tags| tags.add("man_made", "dyke").add("highway", "path"))
+            })
+            .add_landcover("meadow")
+            .add_feature("feature_lines", |b| {
+                b.with("name", "")
+                    .with("type", "dyke")
+                    .with("class", "man_made")
+                    .with("flow_side", 0i16)
+                    .with("tags", HashMap::<String, Option<String>>::new())
+                    .with_line_string(false)
+            })
+            .add_feature("embankment_top_paths", |b| b.with_line_string(false))
+            .add_feature("roads", |b| b.with_road("path"))
+            .build(),
         LegendItem::builder("water_slide", Category::Other, 17, for_taginfo)
             .add_tag_set(|ts| ts.add_tags(|tags| tags.add("attraction", "water_slide")))
             .add_feature("roads", |b| {
@@ -414,6 +629,13 @@ impl PropsBuilder {
             .with("bicycle", "")
             .with("foot", "")
             .with("trail_visibility", 0)
+            .with("sac_scale", 0)
+            .with("conveying", "")
+            .with("piste_grooming", "")
+            .with("layer", "")
+            .with("surface", "")
+            .with("smoothness", "")
+            .with("construction", "")
             .with_line_string(false)
     }
 
@@ -423,6 +645,7 @@ impl PropsBuilder {
             .with("off1", 0i32)
             .with("refs2", "")
             .with("off2", 0i32)
+            .with("shields", "")
             .with("h_red", 0i32)
             .with("h_blue", 0i32)
             .with("h_green", 0i32)
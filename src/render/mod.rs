@@ -1,13 +1,19 @@
 pub use coverage::{TileCoverageRelation, tile_touches_coverage};
 pub use feature::{Feature, FeatureError, GeomError, LegendValue};
 pub use image_format::ImageFormat;
+pub use layers::{LAYER_NAMES, PEAK_TIER_SQL, query_layer};
 pub use legend::{LegendMeta, LegendMode, legend_metadata, legend_render_request};
-pub use render_config::{ContourCountries, HillshadingHierarchy, RenderConfig};
+pub use render_config::{
+    ContourCountries, ContourIntervals, ContourUnit, HillshadingHierarchy, LandcoverMinAreas,
+    RenderConfig,
+};
 pub use render_request::{
-    CustomLayer, CustomLayerOrder, Decorations, Glow, LabelStyle, RenderLayer, RenderRequest,
+    CustomLayer, CustomLayerOrder, Decorations, Glow, Highlight, LabelStyle, PoiFilter,
+    RELATION_ID_OFFSET, RenderLayer, RenderRequest,
 };
 pub use render_worker_pool::RenderWorkerPool;
-pub use xyz::bbox_size_in_pixels;
+pub use svg_repo::shadowed_svgs;
+pub use xyz::{bbox_size_in_pixels, scale_denominator};
 use std::path::PathBuf;
 
 mod categories;
@@ -38,3 +44,7 @@ pub fn set_mapping_path(path: PathBuf) {
 pub fn set_fonts_path(path: PathBuf) {
     draw::font_system::set_fonts_path(path);
 }
+
+pub fn set_font_families(family: String, fallback: Vec<String>) {
+    draw::font_system::set_font_families(family, fallback);
+}
@@ -1,9 +1,6 @@
-use std::{
-    collections::HashSet,
-    path::{Path, PathBuf},
-    str::FromStr,
-    sync::Arc,
-};
+use crate::render::xyz::meters_per_pixel;
+use clap::ValueEnum;
+use std::{collections::HashSet, path::PathBuf, str::FromStr};
 
 #[derive(Clone, Debug)]
 pub struct HillshadingEntry {
@@ -156,11 +153,292 @@ impl FromStr for ContourCountries {
     }
 }
 
+/// Unit of contour intervals and labels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ContourUnit {
+    #[default]
+    Meters,
+    Feet,
+}
+
+/// Contour intervals by zoom. Comma-separated `<min-zoom>:<interval>` entries with ascending
+/// zooms; an interval applies from its zoom up to the next entry. Zooms below the first entry
+/// use the first interval.
+#[derive(Clone, Debug)]
+pub struct ContourIntervals {
+    entries: Vec<(u8, u16)>,
+    unit: ContourUnit,
+}
+
+impl ContourIntervals {
+    #[must_use]
+    pub const fn with_unit(self, unit: ContourUnit) -> Self {
+        Self { unit, ..self }
+    }
+
+    pub const fn unit(&self) -> ContourUnit {
+        self.unit
+    }
+
+    /// Interval for the zoom in [`Self::unit`].
+    pub fn interval(&self, zoom: u8) -> u32 {
+        let interval = self
+            .entries
+            .iter()
+            .take_while(|(min_zoom, _)| *min_zoom <= zoom)
+            .last()
+            .unwrap_or(&self.entries[0])
+            .1;
+
+        u32::from(interval)
+    }
+
+    /// Checks the intervals against the height step of the contour sources in meters. Meter
+    /// intervals must be its multiples, otherwise most of their contours don't exist. Feet
+    /// contours are picked from the source contours nearest to their heights, which only
+    /// works with a 1 m step.
+    pub fn check_source_step(&self, step: u16) -> Result<(), String> {
+        if step == 0 {
+            return Err("contour source step must be positive".into());
+        }
+
+        if self.unit == ContourUnit::Feet {
+            return if step == 1 {
+                Ok(())
+            } else {
+                Err(format!(
+                    "feet contours need a contour source step of 1 m, not {step} m"
+                ))
+            };
+        }
+
+        match self
+            .entries
+            .iter()
+            .find(|(_, interval)| interval % step != 0)
+        {
+            Some((zoom, interval)) => Err(format!(
+                "contour interval {interval} at zoom {zoom} is not a multiple of the source step {step}"
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for ContourIntervals {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut entries: Vec<(u8, u16)> = Vec::new();
+
+        for raw in value.split(',') {
+            let raw = raw.trim();
+
+            let (zoom, interval) = raw.split_once(':').ok_or_else(|| {
+                format!("contour-intervals entry must be <zoom>:<interval>: '{raw}'")
+            })?;
+
+            let zoom: u8 = zoom
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid zoom in contour-intervals entry '{raw}'"))?;
+
+            let interval: u16 = interval
+                .trim()
+                .parse()
+                .ok()
+                .filter(|interval| *interval > 0)
+                .ok_or_else(|| format!("invalid interval in contour-intervals entry '{raw}'"))?;
+
+            if entries
+                .last()
+                .is_some_and(|(last_zoom, _)| *last_zoom >= zoom)
+            {
+                return Err("contour-intervals zooms must be ascending".into());
+            }
+
+            entries.push((zoom, interval));
+        }
+
+        Ok(Self {
+            entries,
+            unit: ContourUnit::Meters,
+        })
+    }
+}
+
+/// Minimum landcover polygon areas by type. Comma-separated `<type>:<max-zoom>:<min-area>`
+/// entries; polygons of the type smaller than `<min-area>` pixels² are skipped up to
+/// `<max-zoom>`. Of more entries of a type, the one with the lowest applicable zoom wins.
+#[derive(Clone, Debug, Default)]
+pub struct LandcoverMinAreas(Vec<(String, u8, f64)>);
+
+impl LandcoverMinAreas {
+    /// Minimum areas of the types at the zoom in EPSG:3857 units², as in the `area` column.
+    pub fn for_zoom(&self, zoom: u8) -> Vec<(String, f64)> {
+        let pixel_area = meters_per_pixel(zoom, 1.0).powi(2);
+
+        let mut entries: Vec<_> = self
+            .0
+            .iter()
+            .filter(|(_, max_zoom, _)| zoom <= *max_zoom)
+            .collect();
+
+        entries.sort_by_key(|(_, max_zoom, _)| *max_zoom);
+
+        let mut min_areas: Vec<(String, f64)> = Vec::new();
+
+        for (typ, _, min_area) in entries {
+            if !min_areas.iter().any(|(seen, _)| seen == typ) {
+                min_areas.push((typ.clone(), min_area * pixel_area));
+            }
+        }
+
+        min_areas
+    }
+}
+
+impl FromStr for LandcoverMinAreas {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut entries = Vec::new();
+
+        for raw in value.split(',') {
+            let raw = raw.trim();
+
+            let mut parts = raw.split(':').map(str::trim);
+
+            let (Some(typ), Some(max_zoom), Some(min_area), None) =
+                (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                return Err(format!(
+                    "landcover-min-areas entry must be <type>:<max-zoom>:<min-area>: '{raw}'"
+                ));
+            };
+
+            if typ.is_empty() {
+                return Err(format!("empty type in landcover-min-areas entry '{raw}'"));
+            }
+
+            let max_zoom: u8 = max_zoom
+                .parse()
+                .map_err(|_| format!("invalid zoom in landcover-min-areas entry '{raw}'"))?;
+
+            let min_area: f64 = min_area
+                .parse()
+                .ok()
+                .filter(|min_area: &f64| min_area.is_finite() && *min_area >= 0.0)
+                .ok_or_else(|| format!("invalid area in landcover-min-areas entry '{raw}'"))?;
+
+            entries.push((typ.to_string(), max_zoom, min_area));
+        }
+
+        Ok(Self(entries))
+    }
+}
+
 /// Static, server-side render configuration that does not vary per request.
 #[derive(Clone, Debug)]
 pub struct RenderConfig {
-    pub svg_base_path: Arc<Path>,
+    /// Directories with symbol SVGs in order of precedence.
+    pub svg_base_paths: Vec<PathBuf>,
     pub hillshading_base_path: Option<PathBuf>,
     pub hillshading_hierarchy: Option<HillshadingHierarchy>,
+    /// Open the hillshading datasets of all the countries at startup.
+    pub preload_hillshading: bool,
     pub contour_countries: Option<ContourCountries>,
+    pub contour_intervals: ContourIntervals,
+    pub landcover_min_areas: LandcoverMinAreas,
+    /// Layers skipped in every render, see [`crate::render::LAYER_NAMES`].
+    pub disabled_layers: HashSet<String>,
+    /// Render a tile once more when a DB connection broke during its render.
+    pub retry_on_connection_error: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contour_interval_by_zoom() {
+        let intervals: ContourIntervals = "12:50,13:20,15:10".parse().expect("intervals");
+
+        assert_eq!(intervals.interval(10), 50);
+        assert_eq!(intervals.interval(12), 50);
+        assert_eq!(intervals.interval(14), 20);
+        assert_eq!(intervals.interval(18), 10);
+    }
+
+    #[test]
+    fn feet_intervals_stay_in_feet() {
+        let intervals = "12:200,15:20"
+            .parse::<ContourIntervals>()
+            .expect("intervals")
+            .with_unit(ContourUnit::Feet);
+
+        assert_eq!(intervals.unit(), ContourUnit::Feet);
+        assert_eq!(intervals.interval(12), 200);
+        assert_eq!(intervals.interval(15), 20);
+    }
+
+    #[test]
+    fn intervals_must_be_multiples_of_source_step() {
+        let intervals: ContourIntervals = "12:50,13:20,15:10".parse().expect("intervals");
+
+        assert!(intervals.check_source_step(10).is_ok());
+        assert!(intervals.check_source_step(20).is_err());
+        assert!(intervals.check_source_step(0).is_err());
+    }
+
+    #[test]
+    fn feet_intervals_need_meter_sources() {
+        let intervals = "12:200,15:20"
+            .parse::<ContourIntervals>()
+            .expect("intervals")
+            .with_unit(ContourUnit::Feet);
+
+        assert!(intervals.check_source_step(1).is_ok());
+        assert!(intervals.check_source_step(10).is_err());
+    }
+
+    #[test]
+    fn invalid_contour_intervals() {
+        assert!("".parse::<ContourIntervals>().is_err());
+        assert!("12:0".parse::<ContourIntervals>().is_err());
+        assert!("13:20,12:50".parse::<ContourIntervals>().is_err());
+    }
+
+    #[test]
+    fn landcover_min_areas_by_zoom() {
+        let min_areas: LandcoverMinAreas = "scrub:11:100, scrub:13:25, grass:12:50"
+            .parse()
+            .expect("min areas");
+
+        let pixel_area = |zoom| meters_per_pixel(zoom, 1.0).powi(2);
+
+        assert_eq!(
+            min_areas.for_zoom(10),
+            [
+                ("scrub".to_string(), 100.0 * pixel_area(10)),
+                ("grass".to_string(), 50.0 * pixel_area(10)),
+            ]
+        );
+
+        assert_eq!(
+            min_areas.for_zoom(13),
+            [("scrub".to_string(), 25.0 * pixel_area(13))]
+        );
+
+        assert!(min_areas.for_zoom(14).is_empty());
+    }
+
+    #[test]
+    fn invalid_landcover_min_areas() {
+        assert!("scrub:13".parse::<LandcoverMinAreas>().is_err());
+        assert!(":13:25".parse::<LandcoverMinAreas>().is_err());
+        assert!("scrub:x:25".parse::<LandcoverMinAreas>().is_err());
+        assert!("scrub:13:-1".parse::<LandcoverMinAreas>().is_err());
+        assert!("scrub:13:25:1".parse::<LandcoverMinAreas>().is_err());
+    }
 }
@@ -1,4 +1,6 @@
-use crate::render::{colors::Color, image_format::ImageFormat, legend::LegendItemData};
+use crate::render::{
+    colors::Color, image_format::ImageFormat, layers::POI_ORDER, legend::LegendItemData,
+};
 use clap::ValueEnum;
 use colorsys::RgbRatio;
 use cosmic_text::Weight;
@@ -8,6 +10,7 @@ use geo::Rect;
 use geojson::Feature;
 use serde::Deserialize;
 use std::collections::HashSet;
+use std::str::FromStr;
 use std::sync::Arc;
 
 #[derive(Debug, Hash, ValueEnum, EnumSetType)]
@@ -18,11 +21,17 @@ pub enum RenderLayer {
     Geonames,
     CountryNames,
     CountryBorders,
+    MaritimeBorders,
+    NationalParkBorderNames,
+    LandcoverBlending,
+    RoofShading,
+    Seamarks,
     RoutesHiking,
     RoutesHikingKst,
     RoutesHorse,
     RoutesBicycle,
     RoutesSki,
+    DebugWikidata,
 }
 
 #[derive(Deserialize, Debug, Clone, Copy)]
@@ -82,6 +91,97 @@ pub struct Decorations {
     pub center_lat: f64,
 }
 
+/// Restricts the POI types rendered by a tile variant. Parsed from
+/// `allow:<type>,<type>…` or `deny:<type>,<type>…`.
+#[derive(Debug, Clone)]
+pub enum PoiFilter {
+    Allow(HashSet<String>),
+    Deny(HashSet<String>),
+}
+
+impl PoiFilter {
+    /// A tiered type (e.g. `peak1`, `volcano3`) also matches its base type (`peak`, `volcano`).
+    pub fn allows(&self, typ: &str) -> bool {
+        let base = typ.trim_end_matches(|c: char| c.is_ascii_digit());
+
+        let listed = |types: &HashSet<String>| types.contains(typ) || types.contains(base);
+
+        match self {
+            Self::Allow(types) => listed(types),
+            Self::Deny(types) => !listed(types),
+        }
+    }
+}
+
+impl FromStr for PoiFilter {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (mode, list) = value
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| format!("POI filter must start with 'allow:' or 'deny:': {value}"))?;
+
+        let mut types = HashSet::new();
+
+        for token in list.split(',') {
+            let typ = token.trim();
+
+            if !POI_ORDER.contains(&typ) {
+                return Err(format!("unknown POI type '{typ}'"));
+            }
+
+            types.insert(typ.to_string());
+        }
+
+        match mode {
+            "allow" => Ok(Self::Allow(types)),
+            "deny" => Ok(Self::Deny(types)),
+            _ => Err(format!("unknown POI filter mode '{mode}'")),
+        }
+    }
+}
+
+/// Offset of relation ids in the single id space of the imported tables.
+pub const RELATION_ID_OFFSET: i64 = -100_000_000_000_000_000;
+
+/// OSM elements to outline on top of the map. Parsed from a comma separated
+/// list of `node/<id>`, `way/<id>` or `relation/<id>` and stored as imposm
+/// `osm_id`s (single id space: ways negated, relations offset by `-1e17`).
+#[derive(Debug, Clone)]
+pub struct Highlight(pub Vec<i64>);
+
+impl FromStr for Highlight {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value
+            .split(',')
+            .map(|token| {
+                let token = token.trim();
+
+                let (typ, id) = token
+                    .split_once('/')
+                    .ok_or_else(|| format!("expected <type>/<id>: {token}"))?;
+
+                let id = id
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|id| *id > 0)
+                    .ok_or_else(|| format!("invalid OSM id: {token}"))?;
+
+                match typ {
+                    "node" => Ok(id),
+                    "way" => Ok(-id),
+                    "relation" => Ok(RELATION_ID_OFFSET - id),
+                    _ => Err(format!("unknown OSM element type '{typ}'")),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RenderRequest {
     pub bbox: Rect<f64>,
@@ -93,6 +193,8 @@ pub struct RenderRequest {
     pub custom_layer: Option<CustomLayer>,
     pub legend: Option<LegendItemData>,
     pub decorations: Option<Decorations>,
+    pub poi_filter: Option<Arc<PoiFilter>>,
+    pub highlight: Option<Highlight>,
 }
 
 impl RenderRequest {
@@ -114,6 +216,28 @@ impl RenderRequest {
             custom_layer: None,
             legend: None,
             decorations: None,
+            poi_filter: None,
+            highlight: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poi_filter_matches_tiered_types_by_base_type() {
+        let allow: PoiFilter = "allow:peak".parse().expect("valid filter");
+
+        assert!(allow.allows("peak"));
+        assert!(allow.allows("peak2"));
+        assert!(!allow.allows("volcano1"));
+
+        let deny: PoiFilter = "deny:volcano,peak1".parse().expect("valid filter");
+
+        assert!(!deny.allows("volcano3"));
+        assert!(!deny.allows("peak1"));
+        assert!(deny.allows("peak2"));
+    }
+}
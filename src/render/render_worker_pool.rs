@@ -1,5 +1,7 @@
 use crate::render::{
-    self, RenderConfig, RenderRequest, layers::load_hillshading_datasets, renderer::RenderError,
+    self, RenderConfig, RenderRequest,
+    layers::{load_hillshading_datasets, report_preloaded_datasets},
+    renderer::RenderError,
     svg_repo::SvgRepo,
 };
 use deadpool_postgres::Pool;
@@ -40,6 +42,7 @@ impl RenderWorkerPool {
         pool: Pool,
         handle: Handle,
         worker_count: usize,
+        pin_workers: bool,
         config: Arc<RenderConfig>,
     ) -> Self {
         let queue_size = worker_count.max(1) * 2;
@@ -47,17 +50,56 @@ impl RenderWorkerPool {
         let rx = Arc::new(Mutex::new(rx));
         let mut workers = Vec::with_capacity(worker_count);
 
+        let core_ids = if pin_workers {
+            core_affinity::get_core_ids().unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        if pin_workers && core_ids.is_empty() {
+            eprintln!("Thread affinity is not supported, render workers are not pinned.");
+        }
+
+        let preloaded_countries: Vec<&'static str> = if config.preload_hillshading
+            && let Some(hierarchy) = config.hillshading_hierarchy.as_ref()
+        {
+            hierarchy
+                .entries()
+                .iter()
+                .map(|entry| entry.country)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if let Some(hillshading_base_path) = config.hillshading_base_path.as_ref()
+            && !preloaded_countries.is_empty()
+        {
+            report_preloaded_datasets(hillshading_base_path, preloaded_countries.iter().copied());
+        }
+
         for worker_id in 0..worker_count {
             let rx = rx.clone();
             let pool = pool.clone();
             let handle = handle.clone();
             let config = config.clone();
+            let preloaded_countries = preloaded_countries.clone();
+
+            let core_id = (!core_ids.is_empty()).then(|| core_ids[worker_id % core_ids.len()]);
 
             let jh = std::thread::Builder::new()
                 .name(format!("render-worker-{worker_id}"))
                 .spawn(move || {
-                    let mut svg_repo =
-                        SvgRepo::new(config.svg_base_path.as_ref().to_path_buf());
+                    if let Some(core_id) = core_id
+                        && !core_affinity::set_for_current(core_id)
+                    {
+                        eprintln!(
+                            "Failed to pin render worker {worker_id} to core {}",
+                            core_id.id
+                        );
+                    }
+
+                    let mut svg_repo = SvgRepo::new(config.svg_base_paths.clone());
 
                     let mut hillshading_datasets = config
                         .hillshading_base_path
@@ -66,6 +108,11 @@ impl RenderWorkerPool {
                             load_hillshading_datasets(hillshading_base_path)
                         });
 
+                    // every worker has its own datasets as they can't be shared between threads
+                    if let Some(hillshading_datasets) = hillshading_datasets.as_mut() {
+                        hillshading_datasets.preload(preloaded_countries);
+                    }
+
                     loop {
                         let task = {
                             let mut guard = rx.lock().expect("mutex not poisoned");
@@ -76,16 +123,31 @@ impl RenderWorkerPool {
                             break;
                         };
 
-                        let result = render::renderer::render(
-                            &request,
-                            config.hillshading_hierarchy.as_ref(),
-                            config.contour_countries.as_ref(),
-                            pool.clone(),
-                            handle.clone(),
-                            &mut svg_repo,
-                            hillshading_datasets.as_mut(),
-                        )
-                        .map_err(ReError::from);
+                        let mut render = || {
+                            render::renderer::render(
+                                &request,
+                                &config,
+                                pool.clone(),
+                                handle.clone(),
+                                &mut svg_repo,
+                                hillshading_datasets.as_mut(),
+                            )
+                        };
+
+                        let mut result = render();
+
+                        // the broken connection is not returned to the pool, the retry gets
+                        // fresh ones
+                        if config.retry_on_connection_error
+                            && let Err(err) = &result
+                            && err.is_connection_error()
+                        {
+                            eprintln!("Retrying render after a DB connection error: {err}");
+
+                            result = render();
+                        }
+
+                        let result = result.map_err(ReError::from);
 
                         // Ignore send errors (client dropped).
                         let _ = resp_tx.send(result);
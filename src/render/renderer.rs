@@ -1,5 +1,5 @@
 use crate::render::{
-    ContourCountries, HillshadingHierarchy,
+    RenderConfig,
     image_format::ImageFormat,
     layers::{self, HillshadingDatasets},
     render_request::RenderRequest,
@@ -24,10 +24,16 @@ pub enum RenderError {
     ImageEncoding(Box<dyn std::error::Error + Send + Sync>),
 }
 
+impl RenderError {
+    /// Whether the render failed on a broken DB connection, so that it may succeed on another.
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, Self::Layers(err) if err.is_connection_error())
+    }
+}
+
 pub fn render(
     request: &RenderRequest,
-    hillshading_hierarchy: Option<&HillshadingHierarchy>,
-    contour_countries: Option<&ContourCountries>,
+    config: &RenderConfig,
     pool: Pool,
     handle: Handle,
     svg_repo: &mut SvgRepo,
@@ -42,14 +48,17 @@ pub fn render(
             surface,
             request,
             layers::Shading {
-                hierarchy: hillshading_hierarchy,
-                contour_countries,
+                hierarchy: config.hillshading_hierarchy.as_ref(),
+                contour_countries: config.contour_countries.as_ref(),
+                contour_intervals: &config.contour_intervals,
                 datasets: hillshading_datasets,
             },
             pool,
             handle,
             size,
             svg_repo,
+            &config.disabled_layers,
+            &config.landcover_min_areas,
         )
     };
 
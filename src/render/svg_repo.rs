@@ -1,11 +1,16 @@
 use cairo::{Content, RecordingSurface, Rectangle};
 use gio::glib::{self};
 use rsvg::LoadingError;
-use std::{collections::HashMap, fs::read_to_string, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::{read_dir, read_to_string},
+    path::PathBuf,
+};
 use xmltree::{Element, EmitterConfig, XMLNode};
 
 pub struct SvgRepo {
-    base: PathBuf,
+    /// Directories searched for SVGs, in order of precedence.
+    bases: Vec<PathBuf>,
     svg_map: HashMap<String, RecordingSurface>,
 }
 
@@ -45,9 +50,9 @@ impl From<&str> for Options {
 }
 
 impl SvgRepo {
-    pub fn new(base: impl Into<PathBuf>) -> Self {
+    pub fn new(bases: Vec<PathBuf>) -> Self {
         Self {
-            base: base.into(),
+            bases,
             svg_map: HashMap::new(),
         }
     }
@@ -75,7 +80,7 @@ impl SvgRepo {
             let mut main_svg: Option<Element> = None;
 
             for ref name in options.names {
-                let full_path = self.base.join(format!("{name}.svg"));
+                let full_path = resolve(&self.bases, name);
 
                 let input = read_to_string(full_path).map_err(|err| SvgRepoError {
                     msg: format!("Error loading SVG ({name})"),
@@ -241,3 +246,51 @@ impl SvgRepo {
         Ok(svg_map.get(key).expect("svg from map"))
     }
 }
+
+/// Path of the first existing `{name}.svg`; in the last (default) base path if there is none.
+fn resolve(bases: &[PathBuf], name: &str) -> PathBuf {
+    let file_name = format!("{name}.svg");
+
+    bases
+        .iter()
+        .map(|base| base.join(&file_name))
+        .find(|path| path.is_file())
+        .or_else(|| bases.last().map(|base| base.join(&file_name)))
+        .unwrap_or_else(|| PathBuf::from(file_name))
+}
+
+/// SVGs in the overriding directories (all but the last of `bases`) shadowing an SVG of the
+/// same name in a directory of lower precedence.
+pub fn shadowed_svgs(bases: &[PathBuf]) -> Vec<PathBuf> {
+    let Some((_, overrides)) = bases.split_last() else {
+        return vec![];
+    };
+
+    let mut shadowed = vec![];
+
+    for (i, dir) in overrides.iter().enumerate() {
+        let Ok(entries) = read_dir(dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if path.extension().is_none_or(|ext| ext != "svg") {
+                continue;
+            }
+
+            if let Some(file_name) = path.file_name()
+                && bases[i + 1..]
+                    .iter()
+                    .any(|base| base.join(file_name).is_file())
+            {
+                shadowed.push(path);
+            }
+        }
+    }
+
+    shadowed.sort();
+
+    shadowed
+}
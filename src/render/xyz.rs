@@ -5,6 +5,9 @@ const EARTH_RADIUS: f64 = 6_378_137.0; // Equatorial radius of the Earth in mete
 
 const HALF_CIRCUMFERENCE: f64 = std::f64::consts::PI * EARTH_RADIUS;
 
+/// Size of the OGC "standardized rendering pixel" in meters (0.28 mm).
+const STANDARD_PIXEL_SIZE: f64 = 0.00028;
+
 pub fn bbox_size_in_pixels(bbox: Rect<f64>, zoom: f64) -> Size<u32> {
     let resolution = 2.0 * HALF_CIRCUMFERENCE / (256.0 * zoom.exp2());
 
@@ -14,6 +17,16 @@ pub fn bbox_size_in_pixels(bbox: Rect<f64>, zoom: f64) -> Size<u32> {
     )
 }
 
+/// Meters per pixel at the equator of tiles rendered at `scale` (e.g. 2 for `@2x` tiles).
+pub fn meters_per_pixel(zoom: u8, scale: f64) -> f64 {
+    2.0 * HALF_CIRCUMFERENCE / (256.0 * f64::from(zoom).exp2() * scale)
+}
+
+/// OGC scale denominator at the equator, as in the tile matrix sets of the WMTS capabilities.
+pub fn scale_denominator(zoom: u8, scale: f64) -> f64 {
+    meters_per_pixel(zoom, scale) / STANDARD_PIXEL_SIZE
+}
+
 pub fn to_absolute_pixel_coords(x: f64, y: f64, zoom: u8) -> (f64, f64) {
     // Tile size in pixels (usually 256 or 512)
     let tile_size: f64 = 256.0;
@@ -48,3 +61,15 @@ pub fn perpendicular_distance(point1: (f64, f64), point2: (f64, f64), theta: f64
     // Cross product in 2D (extended to 3D): a_x * b_y - a_y * b_x
     v.0.mul_add(d.1, -(v.1 * d.0))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_denominator_matches_wmts_tile_matrices() {
+        assert!((scale_denominator(0, 1.0) - 559_082_264.029).abs() < 0.01);
+        assert!((scale_denominator(0, 2.0) - 279_541_132.014).abs() < 0.01);
+        assert!((scale_denominator(19, 2.0) - 533.182_395_96).abs() < 0.001);
+    }
+}